@@ -0,0 +1,876 @@
+//! `cy-dog`: headless access to the same index the desktop app uses.
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use cy_dog_core::{
+    answer_question, evaluate_answers, evaluate_retrieval, ingest_folder, load_gold_set,
+    load_questions, render_answer_table, render_retrieval_table, run_batch, write_answer_report,
+    write_report, write_retrieval_report, CollectionInfo, CollectionStore, ColumnRole,
+    DatasetSchema, IndexStore, LoadedIndex, McpServer, NumberFormat, Provider, ProviderConfig,
+    ProviderKind, ReportFormat, DEFAULT_CHAT_MODEL, TOP_K_RESULTS,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "cy-dog",
+    version,
+    about = "Customer Insights & Discovery RAG Terminal"
+)]
+struct Cli {
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Index file to use instead of the desktop app's
+    #[arg(long, global = true, env = "CY_DOG_INDEX")]
+    index: Option<PathBuf>,
+
+    /// Collection to use (defaults to the one active in the desktop app)
+    #[arg(long, global = true, env = "CY_DOG_COLLECTION")]
+    collection: Option<String>,
+
+    #[command(flatten)]
+    provider: ProviderArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct ProviderArgs {
+    /// LLM backend to use (defaults to the one chosen in the desktop app)
+    #[arg(long, global = true, value_enum)]
+    provider: Option<ProviderArg>,
+
+    /// Backend base URL (defaults to the desktop app's, or the local Ollama API)
+    #[arg(long, global = true, env = "CY_DOG_BASE_URL")]
+    base_url: Option<String>,
+
+    /// API key for OpenAI-compatible servers (defaults to the desktop app's)
+    #[arg(long, global = true, env = "CY_DOG_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Embedding model (defaults to the one the collection was built with, or the desktop app's)
+    #[arg(long, global = true)]
+    embedding_model: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ProviderArg {
+    Ollama,
+    OpenaiCompatible,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index every supported file (spreadsheets, JSON, notes, transcripts and PDFs) in a folder
+    Ingest {
+        /// Folder containing the files to index
+        folder: PathBuf,
+    },
+    /// Ask a question about the indexed data
+    Ask {
+        /// The question to answer
+        question: String,
+        /// Chat model to answer with (defaults to the collection's)
+        #[arg(long, short)]
+        model: Option<String>,
+        /// Number of rows to retrieve as context
+        #[arg(long, default_value_t = TOP_K_RESULTS)]
+        top_k: usize,
+    },
+    /// Answer a file of questions and write a report
+    Batch {
+        /// Questions file (.txt one per line, .csv or .yaml)
+        questions: PathBuf,
+        /// Report file, given the format's extension; a .json with the same name is written next to it
+        #[arg(long, short)]
+        output: PathBuf,
+        /// Report format
+        #[arg(long, value_enum, default_value_t = ReportArg::Markdown)]
+        format: ReportArg,
+        /// Chat model to answer with (defaults to the collection's)
+        #[arg(long, short)]
+        model: Option<String>,
+        /// Number of rows to retrieve as context
+        #[arg(long, default_value_t = TOP_K_RESULTS)]
+        top_k: usize,
+    },
+    /// List available chat models
+    Models,
+    /// Show backend and index status
+    Status,
+    /// List collections (the active one is marked with *)
+    Collections,
+    /// Show or change how each column is used when indexing
+    Columns {
+        /// Set a column's role: text, metadata, interviewee, date or ignored
+        #[arg(long = "set", value_name = "COLUMN=ROLE")]
+        set: Vec<String>,
+        /// Apply --set to this file only
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Show or change which workbook sheets are indexed
+    Sheets {
+        /// Index this sheet of --file
+        #[arg(long, value_name = "SHEET", requires = "file")]
+        include: Vec<String>,
+        /// Skip this sheet of --file
+        #[arg(long, value_name = "SHEET", requires = "file")]
+        exclude: Vec<String>,
+        /// Workbook the sheets belong to
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Show or change how the numbers of workbook columns are written
+    Numbers {
+        /// Set a column's format: plain, decimals:N, percent or percent:N
+        #[arg(long = "set", value_name = "COLUMN=FORMAT")]
+        set: Vec<String>,
+    },
+    /// Serve the index to MCP clients over stdio
+    Mcp,
+    /// Measure retrieval or answer quality against a gold set
+    Eval {
+        #[command(subcommand)]
+        command: EvalCommand,
+    },
+    /// Export the indexed rows
+    Export {
+        /// Output file (stdout if omitted)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum EvalCommand {
+    /// Score retrieval (recall@k, MRR, nDCG) per embedding model and top-k
+    Retrieval {
+        /// Folder with the dataset to index
+        folder: PathBuf,
+        /// Gold set (.yaml or .json) of questions and their expected rows
+        gold: PathBuf,
+        /// Embedding models to compare (defaults to --embedding-model)
+        #[arg(long, value_delimiter = ',')]
+        embedding_models: Vec<String>,
+        /// Top-k values to score
+        #[arg(long, value_delimiter = ',', default_value = "1,3,5,10")]
+        top_k: Vec<usize>,
+        /// Write the Markdown table here (plus a .json next to it)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Score answers from several chat models with a judge model
+    Answers {
+        /// Questions file (.txt one per line, .csv or .yaml)
+        questions: PathBuf,
+        /// Chat models to compare
+        #[arg(long, value_delimiter = ',', required = true)]
+        models: Vec<String>,
+        /// Model that grades the answers (ideally stronger than the ones compared)
+        #[arg(long)]
+        judge_model: String,
+        /// Number of rows to retrieve as context
+        #[arg(long, default_value_t = TOP_K_RESULTS)]
+        top_k: usize,
+        /// Write the Markdown table here (plus a .json next to it)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportArg {
+    Markdown,
+    Html,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// Status report printed by `cy-dog status`
+#[derive(Serialize)]
+struct StatusReport {
+    provider: cy_dog_core::ProviderStatus,
+    collection: Option<String>,
+    index_path: String,
+    is_indexed: bool,
+    document_count: usize,
+    data_folder: Option<String>,
+}
+
+/// One exported row
+#[derive(Serialize)]
+struct ExportedRow<'a> {
+    id: &'a str,
+    source_file: &'a str,
+    row_number: usize,
+    content: &'a str,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let json = cli.json;
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if json {
+                let error = serde_json::json!({ "error": format!("{:#}", e) });
+                println!("{}", error);
+            } else {
+                eprintln!("error: {:#}", e);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// What every subcommand needs: the backend, the index location and the output mode
+struct Session {
+    provider: Provider,
+    store: IndexStore,
+    collections: CollectionStore,
+    /// The collection in use (None when `--index` names a file directly)
+    collection: Option<CollectionInfo>,
+    json: bool,
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let collections = CollectionStore::default_location();
+    let (store, collection) = match cli.index {
+        Some(path) => (IndexStore::new(path), None),
+        None => {
+            let collection = match &cli.collection {
+                Some(id) => collections.get(id)?,
+                None => collections.active()?,
+            };
+            (collections.index_store(&collection.id), Some(collection))
+        }
+    };
+
+    // Start from the desktop app's settings; queries must be embedded with
+    // the model the collection's index was built with
+    let saved = ProviderConfig::load(collections.dir())?;
+    let index_model = collection
+        .as_ref()
+        .filter(|_| store.path().exists())
+        .map(|c| c.embedding_model.clone());
+
+    let session = Session {
+        provider: Provider::new(cli.provider.to_config(saved, index_model)?),
+        store,
+        collections,
+        collection,
+        json: cli.json,
+    };
+
+    match cli.command {
+        Command::Ingest { folder } => ingest(&session, folder).await,
+        Command::Ask {
+            question,
+            model,
+            top_k,
+        } => ask(&session, &question, &session.chat_model(model), top_k).await,
+        Command::Batch {
+            questions,
+            output,
+            format,
+            model,
+            top_k,
+        } => {
+            let model = session.chat_model(model);
+            batch(&session, &questions, &output, format, &model, top_k).await
+        }
+        Command::Models => models(&session).await,
+        Command::Status => status(&session).await,
+        Command::Collections => list_collections(&session),
+        Command::Columns { set, file } => columns(&session, &set, file.as_deref()),
+        Command::Sheets {
+            include,
+            exclude,
+            file,
+        } => sheets(&session, &include, &exclude, file.as_deref()),
+        Command::Numbers { set } => numbers(&session, &set),
+        Command::Mcp => mcp(&session).await,
+        Command::Eval { command } => match command {
+            EvalCommand::Retrieval {
+                folder,
+                gold,
+                embedding_models,
+                top_k,
+                output,
+            } => eval_retrieval(&session, &folder, &gold, embedding_models, &top_k, output).await,
+            EvalCommand::Answers {
+                questions,
+                models,
+                judge_model,
+                top_k,
+                output,
+            } => eval_answers(&session, &questions, &models, &judge_model, top_k, output).await,
+        },
+        Command::Export { output, format } => export(&session, output, format),
+    }
+}
+
+impl Session {
+    /// Load the persisted index, failing with a hint if there is none
+    fn load_index(&self) -> Result<LoadedIndex> {
+        self.store
+            .load(&self.provider)?
+            .context("No data has been indexed yet. Run `cy-dog ingest <folder>` first.")
+    }
+
+    /// The chat model asked for, else the one last used with the collection
+    fn chat_model(&self, model: Option<String>) -> String {
+        model
+            .or_else(|| self.collection.as_ref()?.chat_model.clone())
+            .unwrap_or_else(|| DEFAULT_CHAT_MODEL.to_string())
+    }
+
+    /// Column roles of the collection in use (detected afresh with `--index`)
+    fn schema(&self) -> DatasetSchema {
+        self.collection
+            .as_ref()
+            .map(|c| c.schema.clone())
+            .unwrap_or_default()
+    }
+}
+
+async fn ingest(session: &Session, folder: PathBuf) -> Result<()> {
+    let folder = folder.to_string_lossy().to_string();
+    let mut schema = session.schema();
+    let ingestion = ingest_folder(&folder, &session.provider, &mut schema).await?;
+
+    if let Some(index) = &ingestion.index {
+        session.store.save(index, Some(&folder))?;
+
+        // Keep the desktop app's collection list in step
+        if let Some(collection) = &session.collection {
+            session.collections.modify(&collection.id, |info| {
+                info.folders = vec![folder.clone()];
+                info.files.clear();
+                info.embedding_model = index.embedding_model_name().to_string();
+                info.document_count = index.len();
+                info.schema = schema;
+            })?;
+        }
+    }
+
+    if session.json {
+        print_json(&ingestion.result)?;
+    } else {
+        println!("{}", ingestion.result.message);
+        for (file, dialect) in &ingestion.result.dialects {
+            if !dialect.is_default() {
+                eprintln!("note: {} read as {}", file, dialect);
+            }
+        }
+        for warning in &ingestion.result.warnings {
+            eprintln!("warning: {}", warning);
+        }
+    }
+
+    if !ingestion.result.success {
+        anyhow::bail!("nothing was indexed");
+    }
+    Ok(())
+}
+
+async fn ask(session: &Session, question: &str, model: &str, top_k: usize) -> Result<()> {
+    let loaded = session.load_index()?;
+    let result = answer_question(&loaded.index, &session.provider, model, question, top_k).await?;
+
+    if session.json {
+        return print_json(&result);
+    }
+
+    println!("{}", result.answer);
+    if !result.sources.is_empty() {
+        println!("\nSources:");
+        for source in &result.sources {
+            println!("  - {}", source);
+        }
+    }
+    Ok(())
+}
+
+async fn batch(
+    session: &Session,
+    questions_path: &Path,
+    output: &Path,
+    format: ReportArg,
+    model: &str,
+    top_k: usize,
+) -> Result<()> {
+    let questions = load_questions(questions_path)?;
+    if questions.is_empty() {
+        anyhow::bail!("No questions found in {}", questions_path.display());
+    }
+
+    let loaded = session.load_index()?;
+    let format = match format {
+        ReportArg::Markdown => ReportFormat::Markdown,
+        ReportArg::Html => ReportFormat::Html,
+    };
+
+    if !session.json {
+        eprintln!(
+            "Answering {} question(s) with {}...",
+            questions.len(),
+            model
+        );
+    }
+    let report = run_batch(&loaded.index, &session.provider, model, &questions, top_k).await;
+    let written = write_report(&report, output, format)?;
+
+    if session.json {
+        return print_json(&written);
+    }
+
+    println!(
+        "Answered {} question(s) ({} failed)",
+        written.questions, written.failures
+    );
+    println!("Report: {}", written.report_path);
+    println!("JSON:   {}", written.json_path);
+    Ok(())
+}
+
+async fn models(session: &Session) -> Result<()> {
+    let models = session.provider.list_chat_models().await?;
+
+    if session.json {
+        return print_json(&models);
+    }
+
+    for model in &models {
+        let details: Vec<String> = [
+            model.parameter_size.clone(),
+            model.quantization_level.clone(),
+            model.context_length.map(|c| format!("{} ctx", c)),
+            model.is_loaded.then(|| "loaded".to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        println!("{}\t{}", model.name, details.join(", "));
+    }
+    Ok(())
+}
+
+async fn status(session: &Session) -> Result<()> {
+    let loaded = session.store.load(&session.provider)?;
+    let report = StatusReport {
+        provider: session.provider.status().await,
+        collection: session.collection.as_ref().map(|c| c.name.clone()),
+        index_path: session.store.path().display().to_string(),
+        is_indexed: loaded.is_some(),
+        document_count: loaded.as_ref().map_or(0, |l| l.index.len()),
+        data_folder: loaded.and_then(|l| l.data_folder),
+    };
+
+    if session.json {
+        return print_json(&report);
+    }
+
+    println!("Backend:    {}", report.provider.message);
+    if let Some(collection) = &report.collection {
+        println!("Collection: {}", collection);
+    }
+    println!("Index:      {}", report.index_path);
+    println!("Documents:  {}", report.document_count);
+    if let Some(folder) = &report.data_folder {
+        println!("Folder:     {}", folder);
+    }
+    Ok(())
+}
+
+fn list_collections(session: &Session) -> Result<()> {
+    let collections = session.collections.list()?;
+
+    if session.json {
+        return print_json(&collections);
+    }
+
+    let active = session.collections.active()?;
+    for collection in &collections {
+        let marker = if collection.id == active.id { "*" } else { " " };
+        println!(
+            "{} {}\t{}\t{} rows\t{}",
+            marker,
+            collection.id,
+            collection.name,
+            collection.document_count,
+            collection.embedding_model
+        );
+    }
+    Ok(())
+}
+
+fn columns(session: &Session, set: &[String], file: Option<&str>) -> Result<()> {
+    let collection = session
+        .collection
+        .as_ref()
+        .context("Column roles belong to a collection and cannot be used with --index")?;
+
+    let mut changes = Vec::with_capacity(set.len());
+    for assignment in set {
+        let (column, role) = assignment
+            .rsplit_once('=')
+            .with_context(|| format!("Expected COLUMN=ROLE, got \"{}\"", assignment))?;
+        changes.push((column.trim().to_string(), role.parse::<ColumnRole>()?));
+    }
+
+    let schema = if changes.is_empty() {
+        collection.schema.clone()
+    } else {
+        session
+            .collections
+            .modify(&collection.id, |info| {
+                for (column, role) in &changes {
+                    info.schema.set_role(file, column, *role);
+                }
+            })?
+            .schema
+    };
+
+    if session.json {
+        return print_json(&schema);
+    }
+
+    if schema.columns.is_empty() && schema.files.is_empty() {
+        println!("No columns yet. Run `cy-dog ingest <folder>` to detect them.");
+        return Ok(());
+    }
+    for (column, role) in &schema.columns {
+        println!("{:<12}{}", role.as_str(), column);
+    }
+    for (file_name, columns) in &schema.files {
+        for (column, role) in columns {
+            println!("{:<12}{} ({})", role.as_str(), column, file_name);
+        }
+    }
+    if !changes.is_empty() {
+        eprintln!("Run `cy-dog ingest` again to apply the new roles.");
+    }
+    Ok(())
+}
+
+fn sheets(
+    session: &Session,
+    include: &[String],
+    exclude: &[String],
+    file: Option<&str>,
+) -> Result<()> {
+    let collection = session
+        .collection
+        .as_ref()
+        .context("Sheet selection belongs to a collection and cannot be used with --index")?;
+
+    let changed = !include.is_empty() || !exclude.is_empty();
+    let schema = match file {
+        Some(file) if changed => {
+            session
+                .collections
+                .modify(&collection.id, |info| {
+                    for sheet in include {
+                        info.schema.set_sheet_included(file, sheet, true);
+                    }
+                    for sheet in exclude {
+                        info.schema.set_sheet_included(file, sheet, false);
+                    }
+                })?
+                .schema
+        }
+        _ => collection.schema.clone(),
+    };
+
+    if session.json {
+        return print_json(&schema.sheets);
+    }
+
+    if schema.sheets.is_empty() {
+        println!("No workbooks yet. Run `cy-dog ingest <folder>` to detect their sheets.");
+        return Ok(());
+    }
+    for (file_name, sheets) in &schema.sheets {
+        for (sheet, included) in sheets {
+            let status = if *included { "included" } else { "skipped" };
+            println!("{:<12}{} ({})", status, sheet, file_name);
+        }
+    }
+    if changed {
+        eprintln!("Run `cy-dog ingest` again to apply the new selection.");
+    }
+    Ok(())
+}
+
+fn numbers(session: &Session, set: &[String]) -> Result<()> {
+    let collection = session
+        .collection
+        .as_ref()
+        .context("Number formats belong to a collection and cannot be used with --index")?;
+
+    let mut changes = Vec::with_capacity(set.len());
+    for assignment in set {
+        let (column, format) = assignment
+            .rsplit_once('=')
+            .with_context(|| format!("Expected COLUMN=FORMAT, got \"{}\"", assignment))?;
+        changes.push((column.trim().to_string(), format.parse::<NumberFormat>()?));
+    }
+
+    let schema = if changes.is_empty() {
+        collection.schema.clone()
+    } else {
+        session
+            .collections
+            .modify(&collection.id, |info| {
+                for (column, format) in &changes {
+                    info.schema.set_number_format(column, *format);
+                }
+            })?
+            .schema
+    };
+
+    if session.json {
+        return print_json(&schema.number_formats);
+    }
+
+    if schema.number_formats.is_empty() {
+        println!("All columns use the plain format. Use --set COLUMN=percent to change one.");
+        return Ok(());
+    }
+    for (column, format) in &schema.number_formats {
+        let format = format.to_string();
+        println!("{:<12}{}", format, column);
+    }
+    if !changes.is_empty() {
+        eprintln!("Run `cy-dog ingest` again to apply the new formats.");
+    }
+    Ok(())
+}
+
+async fn mcp(session: &Session) -> Result<()> {
+    let server = McpServer::new(session.load_index()?.index);
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    // stdout carries the protocol, so diagnostics must go to stderr
+    eprintln!("cy-dog MCP server ready on stdio");
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle(&line).await {
+            stdout.write_all(response.to_string().as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+async fn eval_retrieval(
+    session: &Session,
+    folder: &Path,
+    gold_path: &Path,
+    mut embedding_models: Vec<String>,
+    top_ks: &[usize],
+    output: Option<PathBuf>,
+) -> Result<()> {
+    if top_ks.contains(&0) {
+        anyhow::bail!("--top-k values must be at least 1");
+    }
+
+    let gold = load_gold_set(gold_path)?;
+    if gold.is_empty() {
+        anyhow::bail!("No questions with expected rows in {}", gold_path.display());
+    }
+
+    let config = session.provider.config();
+    if embedding_models.is_empty() {
+        embedding_models.push(config.embedding_model.clone());
+    }
+
+    if !session.json {
+        eprintln!(
+            "Scoring {} question(s) with {} embedding model(s)...",
+            gold.len(),
+            embedding_models.len()
+        );
+    }
+    let folder = folder.to_string_lossy();
+    let evaluation = evaluate_retrieval(
+        &folder,
+        &session.schema(),
+        &gold,
+        config,
+        &embedding_models,
+        top_ks,
+    )
+    .await?;
+
+    match output {
+        Some(path) => {
+            let written = write_retrieval_report(&evaluation, &path)?;
+            if session.json {
+                return print_json(&written);
+            }
+            println!("Report: {}", written.report_path);
+            println!("JSON:   {}", written.json_path);
+        }
+        None if session.json => print_json(&evaluation)?,
+        None => print!("{}", render_retrieval_table(&evaluation)),
+    }
+    Ok(())
+}
+
+async fn eval_answers(
+    session: &Session,
+    questions_path: &Path,
+    models: &[String],
+    judge_model: &str,
+    top_k: usize,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let questions = load_questions(questions_path)?;
+    if questions.is_empty() {
+        anyhow::bail!("No questions found in {}", questions_path.display());
+    }
+
+    let loaded = session.load_index()?;
+
+    if !session.json {
+        eprintln!(
+            "Answering {} question(s) with {} model(s), judged by {}...",
+            questions.len(),
+            models.len(),
+            judge_model
+        );
+    }
+    let evaluation = evaluate_answers(
+        &loaded.index,
+        &session.provider,
+        models,
+        judge_model,
+        &questions,
+        top_k,
+    )
+    .await?;
+
+    match output {
+        Some(path) => {
+            let written = write_answer_report(&evaluation, &path)?;
+            if session.json {
+                return print_json(&written);
+            }
+            println!("Report: {}", written.report_path);
+            println!("JSON:   {}", written.json_path);
+        }
+        None if session.json => print_json(&evaluation)?,
+        None => print!("{}", render_answer_table(&evaluation)),
+    }
+    Ok(())
+}
+
+fn export(session: &Session, output: Option<PathBuf>, format: ExportFormat) -> Result<()> {
+    let loaded = session.load_index()?;
+
+    let rows: Vec<ExportedRow> = loaded
+        .index
+        .documents()
+        .iter()
+        .map(|d| ExportedRow {
+            id: &d.document.id,
+            source_file: &d.document.source_file,
+            row_number: d.document.row_number,
+            content: &d.document.content,
+        })
+        .collect();
+
+    let writer: Box<dyn Write> = match &output {
+        Some(path) => Box::new(
+            std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?,
+        ),
+        None => Box::new(std::io::stdout().lock()),
+    };
+
+    match format {
+        ExportFormat::Json => serde_json::to_writer_pretty(writer, &rows)?,
+        ExportFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            for row in &rows {
+                csv_writer.serialize(row)?;
+            }
+            csv_writer.flush()?;
+        }
+    }
+
+    if let Some(path) = output {
+        eprintln!("Exported {} rows to {}", rows.len(), path.display());
+    }
+    Ok(())
+}
+
+impl ProviderArgs {
+    /// The saved settings with the flags applied; `index_model` is the
+    /// embedding model of the index in use, if there is one
+    fn to_config(
+        &self,
+        saved: ProviderConfig,
+        index_model: Option<String>,
+    ) -> Result<ProviderConfig> {
+        let kind = match self.provider {
+            None => saved.kind,
+            Some(ProviderArg::Ollama) => ProviderKind::Ollama,
+            Some(ProviderArg::OpenaiCompatible) => ProviderKind::OpenAiCompatible,
+        };
+
+        // The saved server and key belong to the saved backend only
+        let mut config = if kind == saved.kind {
+            saved
+        } else if kind == ProviderKind::OpenAiCompatible && self.base_url.is_none() {
+            anyhow::bail!("--base-url is required with --provider openai-compatible")
+        } else {
+            ProviderConfig {
+                kind,
+                ..ProviderConfig::default()
+            }
+        };
+
+        if let Some(base_url) = &self.base_url {
+            config.base_url = base_url.clone();
+        }
+        if let Some(api_key) = &self.api_key {
+            config.api_key = Some(api_key.clone());
+        }
+        if let Some(model) = self.embedding_model.clone().or(index_model) {
+            config.embedding_model = model;
+        }
+        Ok(config)
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
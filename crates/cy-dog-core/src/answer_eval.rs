@@ -0,0 +1,316 @@
+//! Answer quality evaluation: every chat model answers the same questions from
+//! the same retrieved rows, and a judge model scores each answer.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::generation::generate_rag_response;
+use crate::index::{EmbeddableDocument, VectorIndex};
+use crate::provider::Provider;
+use crate::retrieval_eval::{write_evaluation, EvaluationOutput};
+
+/// Lowest and highest score the judge may give
+const SCORE_RANGE: (u8, u8) = (1, 5);
+
+/// Scores the judge gave one answer (1 = poor, 5 = excellent)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JudgeVerdict {
+    /// Every claim is supported by the retrieved rows
+    pub faithfulness: u8,
+    /// Cited rows exist and support the claims they are attached to
+    pub citation_accuracy: u8,
+    /// The answer covers what the rows say about the question
+    pub completeness: u8,
+    #[serde(default)]
+    pub rationale: String,
+}
+
+impl JudgeVerdict {
+    fn overall(&self) -> f64 {
+        (self.faithfulness + self.citation_accuracy + self.completeness) as f64 / 3.0
+    }
+}
+
+/// One model's answer to one question, with the judge's verdict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JudgedAnswer {
+    pub model: String,
+    pub question: String,
+    pub answer: Option<String>,
+    pub sources: Vec<String>,
+    /// Time the chat model took to answer
+    pub latency_ms: u64,
+    pub verdict: Option<JudgeVerdict>,
+    /// Set when generation or judging failed
+    pub error: Option<String>,
+}
+
+/// Averages for one chat model over every judged answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelScore {
+    pub model: String,
+    pub judged: usize,
+    pub failed: usize,
+    pub faithfulness: f64,
+    pub citation_accuracy: f64,
+    pub completeness: f64,
+    pub overall: f64,
+    pub mean_latency_ms: u64,
+}
+
+/// Full result of an answer evaluation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerEvaluation {
+    /// Seconds since the Unix epoch when the run finished
+    pub generated_at: u64,
+    pub judge_model: String,
+    pub top_k: usize,
+    /// Best overall score first
+    pub scores: Vec<ModelScore>,
+    pub answers: Vec<JudgedAnswer>,
+}
+
+/// Answer every question with every model and have `judge_model` score the answers
+pub async fn evaluate_answers(
+    index: &VectorIndex,
+    provider: &Provider,
+    models: &[String],
+    judge_model: &str,
+    questions: &[String],
+    top_k: usize,
+) -> Result<AnswerEvaluation> {
+    let mut answers = Vec::with_capacity(models.len() * questions.len());
+
+    for question in questions {
+        // Retrieve once so every model sees exactly the same rows
+        let docs = index.search(question, top_k).await?;
+
+        for model in models {
+            answers.push(judge_answer(provider, model, judge_model, question, &docs).await);
+        }
+    }
+
+    let mut scores: Vec<ModelScore> = models
+        .iter()
+        .map(|model| summarize(model, &answers))
+        .collect();
+    scores.sort_by(|a, b| b.overall.total_cmp(&a.overall));
+
+    Ok(AnswerEvaluation {
+        generated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        judge_model: judge_model.to_string(),
+        top_k,
+        scores,
+        answers,
+    })
+}
+
+async fn judge_answer(
+    provider: &Provider,
+    model: &str,
+    judge_model: &str,
+    question: &str,
+    docs: &[EmbeddableDocument],
+) -> JudgedAnswer {
+    let mut judged = JudgedAnswer {
+        model: model.to_string(),
+        question: question.to_string(),
+        answer: None,
+        sources: Vec::new(),
+        latency_ms: 0,
+        verdict: None,
+        error: None,
+    };
+
+    let started = Instant::now();
+    let (answer, sources) =
+        match generate_rag_response(question, docs.to_vec(), provider, model).await {
+            Ok(response) => response,
+            Err(e) => {
+                judged.error = Some(e.to_string());
+                return judged;
+            }
+        };
+    judged.latency_ms = started.elapsed().as_millis() as u64;
+
+    let verdict = match provider
+        .prompt(
+            judge_model,
+            JUDGE_PREAMBLE,
+            &judge_prompt(question, docs, &answer),
+        )
+        .await
+    {
+        Ok(reply) => parse_verdict(&reply).map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    judged.answer = Some(answer);
+    judged.sources = sources;
+    match verdict {
+        Ok(verdict) => judged.verdict = Some(verdict),
+        Err(e) => judged.error = Some(format!("Judge failed: {}", e)),
+    }
+    judged
+}
+
+fn summarize(model: &str, answers: &[JudgedAnswer]) -> ModelScore {
+    let answers: Vec<&JudgedAnswer> = answers.iter().filter(|a| a.model == model).collect();
+    let verdicts: Vec<&JudgeVerdict> = answers.iter().filter_map(|a| a.verdict.as_ref()).collect();
+    let answered: Vec<u64> = answers
+        .iter()
+        .filter(|a| a.answer.is_some())
+        .map(|a| a.latency_ms)
+        .collect();
+
+    let mean = |score: fn(&JudgeVerdict) -> f64| {
+        if verdicts.is_empty() {
+            0.0
+        } else {
+            verdicts.iter().copied().map(score).sum::<f64>() / verdicts.len() as f64
+        }
+    };
+
+    ModelScore {
+        model: model.to_string(),
+        judged: verdicts.len(),
+        failed: answers.len() - verdicts.len(),
+        faithfulness: mean(|v| v.faithfulness as f64),
+        citation_accuracy: mean(|v| v.citation_accuracy as f64),
+        completeness: mean(|v| v.completeness as f64),
+        overall: mean(JudgeVerdict::overall),
+        mean_latency_ms: answered.iter().sum::<u64>() / answered.len().max(1) as u64,
+    }
+}
+
+fn judge_prompt(question: &str, docs: &[EmbeddableDocument], answer: &str) -> String {
+    let context = docs
+        .iter()
+        .map(|doc| format!("[{}] {}", doc.citation(), doc.content))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "QUESTION:\n{}\n\nRETRIEVED ROWS:\n{}\n\nANSWER TO GRADE:\n{}",
+        question, context, answer
+    )
+}
+
+/// Pull the JSON verdict out of the judge's reply (models often wrap it in prose)
+fn parse_verdict(reply: &str) -> Result<JudgeVerdict> {
+    let invalid = |message: String| Error::InvalidResponse {
+        url: "judge".to_string(),
+        message,
+    };
+
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => {
+            return Err(invalid(format!(
+                "no JSON object in reply: {}",
+                reply.trim()
+            )))
+        }
+    };
+
+    let mut verdict: JudgeVerdict =
+        serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+    let (low, high) = SCORE_RANGE;
+    for score in [
+        &mut verdict.faithfulness,
+        &mut verdict.citation_accuracy,
+        &mut verdict.completeness,
+    ] {
+        *score = (*score).clamp(low, high);
+    }
+    Ok(verdict)
+}
+
+/// Render the model comparison (and failures) as Markdown
+pub fn render_answer_table(evaluation: &AnswerEvaluation) -> String {
+    let mut out = String::new();
+    out.push_str("# Answer Quality Evaluation\n\n");
+    out.push_str(&format!(
+        "Judge: `{}` · Top-k: {} · Scores are 1–5\n\n",
+        evaluation.judge_model, evaluation.top_k
+    ));
+
+    out.push_str(
+        "| Model | Overall | Faithfulness | Citations | Completeness | Mean latency | Judged | Failed |\n",
+    );
+    out.push_str("|---|---:|---:|---:|---:|---:|---:|---:|\n");
+    for score in &evaluation.scores {
+        out.push_str(&format!(
+            "| {} | {:.2} | {:.2} | {:.2} | {:.2} | {:.1} s | {} | {} |\n",
+            score.model,
+            score.overall,
+            score.faithfulness,
+            score.citation_accuracy,
+            score.completeness,
+            score.mean_latency_ms as f64 / 1000.0,
+            score.judged,
+            score.failed
+        ));
+    }
+
+    let failures: Vec<&JudgedAnswer> = evaluation
+        .answers
+        .iter()
+        .filter(|a| a.error.is_some())
+        .collect();
+    if !failures.is_empty() {
+        out.push_str("\n## Failures\n\n");
+        for failure in failures {
+            out.push_str(&format!(
+                "- `{}` on \"{}\": {}\n",
+                failure.model,
+                failure.question,
+                failure.error.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+
+    out
+}
+
+/// Write the Markdown table plus a JSON file with every answer and verdict
+pub fn write_answer_report(
+    evaluation: &AnswerEvaluation,
+    output_path: &Path,
+) -> Result<EvaluationOutput> {
+    write_evaluation(evaluation, &render_answer_table(evaluation), output_path)
+}
+
+/// Instructions for the judge model
+const JUDGE_PREAMBLE: &str = r#"You grade answers written by an assistant that analyzes customer discovery interview data. The assistant was given only the RETRIEVED ROWS below and had to cite them as "<file>, Row <n>".
+
+Score the ANSWER TO GRADE from 1 (poor) to 5 (excellent) on:
+- faithfulness: every claim is supported by the retrieved rows; nothing is invented
+- citation_accuracy: the cited rows exist in the retrieved rows and support the claims they are attached to
+- completeness: the answer uses all relevant information the rows contain about the question
+
+Reply with a single JSON object and nothing else:
+{"faithfulness": <1-5>, "citation_accuracy": <1-5>, "completeness": <1-5>, "rationale": "<one sentence>"}"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict_from_wrapped_reply() {
+        let reply = "Here is my assessment:\n```json\n{\"faithfulness\": 5, \"citation_accuracy\": 9, \"completeness\": 3, \"rationale\": \"Misses one row.\"}\n```";
+
+        let verdict = parse_verdict(reply).unwrap();
+        assert_eq!(verdict.faithfulness, 5);
+        assert_eq!(verdict.citation_accuracy, 5);
+        assert_eq!(verdict.completeness, 3);
+        assert!((verdict.overall() - 13.0 / 3.0).abs() < 1e-9);
+
+        assert!(parse_verdict("I cannot grade this.").is_err());
+    }
+}
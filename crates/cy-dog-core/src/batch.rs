@@ -0,0 +1,441 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::generation::generate_rag_response;
+use crate::index::VectorIndex;
+use crate::provider::Provider;
+
+/// Output format of the human-readable batch report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// A retrieved row quoted in the report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    /// "file, Row N" as shown in the chat UI
+    pub source: String,
+    /// The flattened row content that was given to the model
+    pub content: String,
+}
+
+/// The answer to one question of a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchEntry {
+    pub question: String,
+    pub answer: Option<String>,
+    pub citations: Vec<Citation>,
+    /// Set when retrieval or generation failed for this question
+    pub error: Option<String>,
+}
+
+/// All answers of a batch run, as written to the JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchReport {
+    /// Seconds since the Unix epoch when the run finished
+    pub generated_at: u64,
+    pub model: String,
+    pub top_k: usize,
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+    /// Number of questions that failed
+    pub fn failures(&self) -> usize {
+        self.entries.iter().filter(|e| e.error.is_some()).count()
+    }
+}
+
+/// Where a batch report was written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchOutput {
+    pub questions: usize,
+    pub failures: usize,
+    pub report_path: String,
+    pub json_path: String,
+}
+
+/// Read questions from a .txt (one per line), .csv ("question" column or the
+/// first column) or .yaml/.yml (list of strings or `questions:` list) file
+pub fn load_questions(path: &Path) -> Result<Vec<String>> {
+    let file = path.display().to_string();
+    let parse_error = |message: String| Error::Parse {
+        file: file.clone(),
+        row: None,
+        message,
+    };
+
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let questions = match extension.as_str() {
+        "csv" => {
+            let mut reader = csv::ReaderBuilder::new()
+                .flexible(true)
+                .from_path(path)
+                .map_err(|e| parse_error(e.to_string()))?;
+
+            let column = reader
+                .headers()
+                .map_err(|e| parse_error(e.to_string()))?
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case("question"))
+                .unwrap_or(0);
+
+            let mut questions = Vec::new();
+            for (row_idx, record) in reader.records().enumerate() {
+                let record = record.map_err(|e| Error::Parse {
+                    file: file.clone(),
+                    row: Some(row_idx + 1),
+                    message: e.to_string(),
+                })?;
+                if let Some(question) = record.get(column) {
+                    questions.push(question.to_string());
+                }
+            }
+            questions
+        }
+        "yaml" | "yml" => {
+            let text = fs::read_to_string(path).map_err(|source| Error::Io {
+                path: file.clone(),
+                source,
+            })?;
+            let parsed: QuestionsFile =
+                serde_yaml::from_str(&text).map_err(|e| parse_error(e.to_string()))?;
+            parsed.into_questions()
+        }
+        _ => fs::read_to_string(path)
+            .map_err(|source| Error::Io {
+                path: file.clone(),
+                source,
+            })?
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .map(str::to_string)
+            .collect(),
+    };
+
+    Ok(questions
+        .into_iter()
+        .map(|q| q.trim().to_string())
+        .filter(|q| !q.is_empty())
+        .collect())
+}
+
+/// Accepted YAML layouts for a question list
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuestionsFile {
+    List(Vec<QuestionItem>),
+    Keyed { questions: Vec<QuestionItem> },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuestionItem {
+    Text(String),
+    Object { question: String },
+}
+
+impl QuestionsFile {
+    fn into_questions(self) -> Vec<String> {
+        let items = match self {
+            Self::List(items) | Self::Keyed { questions: items } => items,
+        };
+        items
+            .into_iter()
+            .map(|item| match item {
+                QuestionItem::Text(question) | QuestionItem::Object { question } => question,
+            })
+            .collect()
+    }
+}
+
+/// Answer every question against the index, recording failures per question
+pub async fn run_batch(
+    index: &VectorIndex,
+    provider: &Provider,
+    model_name: &str,
+    questions: &[String],
+    top_k: usize,
+) -> BatchReport {
+    let mut entries = Vec::with_capacity(questions.len());
+
+    for question in questions {
+        entries.push(answer_for_report(index, provider, model_name, question, top_k).await);
+    }
+
+    BatchReport {
+        generated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        model: model_name.to_string(),
+        top_k,
+        entries,
+    }
+}
+
+async fn answer_for_report(
+    index: &VectorIndex,
+    provider: &Provider,
+    model_name: &str,
+    question: &str,
+    top_k: usize,
+) -> BatchEntry {
+    let failed = |e: Error, citations| BatchEntry {
+        question: question.to_string(),
+        answer: None,
+        citations,
+        error: Some(e.to_string()),
+    };
+
+    let docs = match index.search(question, top_k).await {
+        Ok(docs) => docs,
+        Err(e) => return failed(e, Vec::new()),
+    };
+
+    let citations: Vec<Citation> = docs
+        .iter()
+        .map(|doc| Citation {
+            source: doc.citation(),
+            content: doc.content.clone(),
+        })
+        .collect();
+
+    if docs.is_empty() {
+        return BatchEntry {
+            question: question.to_string(),
+            answer: Some("No relevant information found in the indexed data.".to_string()),
+            citations,
+            error: None,
+        };
+    }
+
+    match generate_rag_response(question, docs, provider, model_name).await {
+        Ok((answer, _)) => BatchEntry {
+            question: question.to_string(),
+            answer: Some(answer),
+            citations,
+            error: None,
+        },
+        Err(e) => failed(e, citations),
+    }
+}
+
+/// Render the report as Markdown
+pub fn render_markdown(report: &BatchReport) -> String {
+    let mut out = String::new();
+    out.push_str("# Customer Discovery Batch Report\n\n");
+    out.push_str(&format!(
+        "Model: `{}` · Top-k: {} · Questions: {} · Failures: {}\n\n",
+        report.model,
+        report.top_k,
+        report.entries.len(),
+        report.failures()
+    ));
+
+    for (i, entry) in report.entries.iter().enumerate() {
+        out.push_str(&format!("## {}. {}\n\n", i + 1, entry.question));
+
+        match (&entry.answer, &entry.error) {
+            (_, Some(error)) => out.push_str(&format!("**Error:** {}\n\n", error)),
+            (Some(answer), None) => out.push_str(&format!("{}\n\n", answer.trim())),
+            (None, None) => {}
+        }
+
+        if !entry.citations.is_empty() {
+            out.push_str("### Sources\n\n");
+            for citation in &entry.citations {
+                out.push_str(&format!("- **{}**\n\n", citation.source));
+                for line in citation.content.lines() {
+                    out.push_str(&format!("  > {}\n", line));
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
+
+/// Render the report as a standalone HTML page
+pub fn render_html(report: &BatchReport) -> String {
+    let mut out = String::new();
+    out.push_str(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Customer Discovery Batch Report</title>\n\
+         <style>body{font-family:sans-serif;max-width:50rem;margin:2rem auto;line-height:1.5}\
+         blockquote{color:#555;border-left:3px solid #ccc;margin:0.5rem 0;padding-left:1rem}\
+         .error{color:#b00020}</style>\n</head>\n<body>\n",
+    );
+    out.push_str("<h1>Customer Discovery Batch Report</h1>\n");
+    out.push_str(&format!(
+        "<p>Model: <code>{}</code> · Top-k: {} · Questions: {} · Failures: {}</p>\n",
+        escape_html(&report.model),
+        report.top_k,
+        report.entries.len(),
+        report.failures()
+    ));
+
+    for (i, entry) in report.entries.iter().enumerate() {
+        out.push_str(&format!(
+            "<h2>{}. {}</h2>\n",
+            i + 1,
+            escape_html(&entry.question)
+        ));
+
+        match (&entry.answer, &entry.error) {
+            (_, Some(error)) => out.push_str(&format!(
+                "<p class=\"error\"><strong>Error:</strong> {}</p>\n",
+                escape_html(error)
+            )),
+            (Some(answer), None) => {
+                for paragraph in answer.trim().split("\n\n") {
+                    out.push_str(&format!("<p>{}</p>\n", escape_html(paragraph)));
+                }
+            }
+            (None, None) => {}
+        }
+
+        if !entry.citations.is_empty() {
+            out.push_str("<h3>Sources</h3>\n<ul>\n");
+            for citation in &entry.citations {
+                out.push_str(&format!(
+                    "<li><strong>{}</strong><blockquote>{}</blockquote></li>\n",
+                    escape_html(&citation.source),
+                    escape_html(&citation.content)
+                ));
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Write the report in the requested format plus a JSON file next to it
+pub fn write_report(
+    report: &BatchReport,
+    output_path: &Path,
+    format: ReportFormat,
+) -> Result<BatchOutput> {
+    let rendered = match format {
+        ReportFormat::Markdown => render_markdown(report),
+        ReportFormat::Html => render_html(report),
+    };
+    let (report_path, json_path) =
+        write_with_json(report, &rendered, output_path, format.extension())?;
+
+    Ok(BatchOutput {
+        questions: report.entries.len(),
+        failures: report.failures(),
+        report_path: report_path.display().to_string(),
+        json_path: json_path.display().to_string(),
+    })
+}
+
+/// Write a rendered report and the raw results as JSON, returning both paths.
+/// The report takes `extension` whatever `output_path` ends in, so
+/// `report.json` gives `report.md` and `report.json` rather than one file
+/// overwriting the other.
+pub(crate) fn write_with_json<T: Serialize>(
+    results: &T,
+    rendered: &str,
+    output_path: &Path,
+    extension: &str,
+) -> Result<(PathBuf, PathBuf)> {
+    let report_path = output_path.with_extension(extension);
+    let json_path = output_path.with_extension("json");
+
+    let io_error = |path: &Path, source| Error::Io {
+        path: path.display().to_string(),
+        source,
+    };
+
+    if let Some(parent) = report_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|source| io_error(parent, source))?;
+    }
+    fs::write(&report_path, rendered).map_err(|source| io_error(&report_path, source))?;
+
+    let json = serde_json::to_string_pretty(results).map_err(|e| Error::Io {
+        path: json_path.display().to_string(),
+        source: e.into(),
+    })?;
+    fs::write(&json_path, json).map_err(|source| io_error(&json_path, source))?;
+
+    Ok((report_path, json_path))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_questions_from_yaml() {
+        let path =
+            std::env::temp_dir().join(format!("cy-dog-questions-{}.yaml", std::process::id()));
+        fs::write(
+            &path,
+            "questions:\n  - What frustrates users?\n  - question: How do they onboard?\n",
+        )
+        .unwrap();
+
+        let questions = load_questions(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            questions,
+            vec!["What frustrates users?", "How do they onboard?"]
+        );
+    }
+
+    #[test]
+    fn test_report_and_json_never_share_a_path() {
+        let dir = std::env::temp_dir().join(format!("cy-dog-batch-{}", std::process::id()));
+        let report = BatchReport {
+            generated_at: 0,
+            model: "llama3:8b".to_string(),
+            top_k: 5,
+            entries: Vec::new(),
+        };
+
+        let written =
+            write_report(&report, &dir.join("report.json"), ReportFormat::Markdown).unwrap();
+        assert!(written.report_path.ends_with("report.md"));
+        assert!(written.json_path.ends_with("report.json"));
+        assert!(fs::read_to_string(&written.report_path)
+            .unwrap()
+            .starts_with('#'));
+
+        let written = write_report(&report, &dir.join("report.md"), ReportFormat::Html).unwrap();
+        assert!(written.report_path.ends_with("report.html"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,308 @@
+//! Spreadsheet cell values, typed and rendered.
+//!
+//! Workbook cells are rendered by type rather than with calamine's `Display`:
+//! dates become ISO 8601 (`2024-03-02`) instead of serial numbers, numbers
+//! follow the column's `NumberFormat` and error cells (`#N/A`) are dropped.
+//! The typed value is kept in the row's metadata so numbers and booleans can
+//! be filtered and sorted as such; dates are kept as ISO text, which sorts
+//! chronologically.
+
+use calamine::Data;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// Decimals shown for numbers without a configured format; enough for any
+/// value typed into a sheet while hiding floating point noise
+const MAX_DECIMALS: usize = 10;
+
+/// Seconds in a day, the unit of Excel date and time values
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A metadata value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CellValue {
+    Bool(bool),
+    Number(f64),
+    /// Text, including dates as ISO 8601
+    Text(String),
+}
+
+impl CellValue {
+    /// Whether this is blank text
+    pub fn is_empty(&self) -> bool {
+        matches!(self, CellValue::Text(text) if text.trim().is_empty())
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            CellValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Whether two values are equal, as numbers (also when written as text)
+    /// or case-insensitively as text
+    pub fn matches(&self, other: &CellValue) -> bool {
+        let number = |value: &CellValue| match value {
+            CellValue::Text(text) => text.trim().parse::<f64>().ok(),
+            other => other.as_number(),
+        };
+        match (number(self), number(other)) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string().eq_ignore_ascii_case(&other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellValue::Bool(true) => f.write_str("TRUE"),
+            CellValue::Bool(false) => f.write_str("FALSE"),
+            CellValue::Number(number) => {
+                f.write_str(&format_number(*number, NumberFormat::default()))
+            }
+            CellValue::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(text: &str) -> Self {
+        CellValue::Text(text.to_string())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(text: String) -> Self {
+        CellValue::Text(text)
+    }
+}
+
+impl PartialEq<&str> for CellValue {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            CellValue::Text(text) => text == other,
+            value => value.to_string().as_str() == *other,
+        }
+    }
+}
+
+/// How a column's numbers are written in the embedded text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumberFormat {
+    /// Fixed number of decimals; by default as many as the value has
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<usize>,
+    /// Write fractions as percentages (0.35 as "35%")
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub percent: bool,
+}
+
+impl NumberFormat {
+    pub fn is_default(&self) -> bool {
+        *self == NumberFormat::default()
+    }
+}
+
+/// Written as "plain", "decimals:2", "percent" or "percent:1"
+impl fmt::Display for NumberFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.percent, self.decimals) {
+            (false, None) => f.write_str("plain"),
+            (false, Some(decimals)) => write!(f, "decimals:{}", decimals),
+            (true, None) => f.write_str("percent"),
+            (true, Some(decimals)) => write!(f, "percent:{}", decimals),
+        }
+    }
+}
+
+impl FromStr for NumberFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        let invalid = || {
+            Error::InvalidSetting(format!(
+                "Unknown number format \"{}\" (expected plain, decimals:N, percent or percent:N)",
+                s
+            ))
+        };
+
+        let (kind, decimals) = match s.split_once(':') {
+            Some((kind, decimals)) => {
+                let decimals = decimals.trim().parse::<usize>().map_err(|_| invalid())?;
+                (kind.trim(), Some(decimals))
+            }
+            None => (s.as_str(), None),
+        };
+
+        match (kind, decimals) {
+            ("plain", None) => Ok(NumberFormat::default()),
+            ("decimals", Some(_)) => Ok(NumberFormat {
+                decimals,
+                percent: false,
+            }),
+            ("percent", _) => Ok(NumberFormat {
+                decimals,
+                percent: true,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Text of a number in `format`
+pub fn format_number(number: f64, format: NumberFormat) -> String {
+    let (value, suffix) = if format.percent {
+        (number * 100.0, "%")
+    } else {
+        (number, "")
+    };
+
+    let text = match format.decimals {
+        Some(decimals) => format!("{:.*}", decimals, value),
+        None => {
+            let text = format!("{:.*}", MAX_DECIMALS, value);
+            let text = text.trim_end_matches('0').trim_end_matches('.');
+            if text == "-0" {
+                "0".to_string()
+            } else {
+                text.to_string()
+            }
+        }
+    };
+
+    format!("{}{}", text, suffix)
+}
+
+/// Rendered text and typed value of a workbook cell; empty text and no value
+/// for empty and error cells
+pub fn render_cell(cell: &Data, format: NumberFormat) -> (String, Option<CellValue>) {
+    match cell {
+        Data::Empty | Data::Error(_) => (String::new(), None),
+        Data::String(text) => (text.clone(), Some(CellValue::from(text.trim()))),
+        Data::Bool(value) => typed(CellValue::Bool(*value)),
+        Data::Int(value) => {
+            let value = *value as f64;
+            (format_number(value, format), Some(CellValue::Number(value)))
+        }
+        Data::Float(value) => (
+            format_number(*value, format),
+            Some(CellValue::Number(*value)),
+        ),
+        Data::DateTime(datetime) if datetime.is_duration() => {
+            typed(CellValue::Text(format_duration(datetime.as_f64())))
+        }
+        Data::DateTime(datetime) => match datetime.as_datetime() {
+            // Values below one day (midnight included) are times of day,
+            // whole days are dates
+            Some(date) => {
+                let days = datetime.as_f64();
+                let text = if days < 1.0 {
+                    date.time().to_string()
+                } else if days.fract() == 0.0 {
+                    date.date().to_string()
+                } else {
+                    format!("{}T{}", date.date(), date.time())
+                };
+                typed(CellValue::Text(text))
+            }
+            None => render_cell(&Data::Float(datetime.as_f64()), format),
+        },
+        Data::DateTimeIso(text) | Data::DurationIso(text) => typed(CellValue::from(text.as_str())),
+    }
+}
+
+fn typed(value: CellValue) -> (String, Option<CellValue>) {
+    (value.to_string(), Some(value))
+}
+
+/// "HH:MM:SS" of a duration in days; hours may exceed 24
+fn format_duration(days: f64) -> String {
+    let seconds = (days * SECONDS_PER_DAY).round() as i64;
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    format!(
+        "{}{:02}:{:02}:{:02}",
+        sign,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calamine::{CellErrorType, ExcelDateTime, ExcelDateTimeType};
+
+    fn date(value: f64) -> Data {
+        Data::DateTime(ExcelDateTime::new(
+            value,
+            ExcelDateTimeType::DateTime,
+            false,
+        ))
+    }
+
+    #[test]
+    fn test_render_cells_by_type() {
+        let plain = NumberFormat::default();
+        assert_eq!(render_cell(&date(45353.0), plain).0, "2024-03-02");
+        assert_eq!(render_cell(&date(45353.75), plain).0, "2024-03-02T18:00:00");
+        assert_eq!(render_cell(&date(0.5), plain).0, "12:00:00");
+        assert_eq!(render_cell(&date(0.0), plain).0, "00:00:00");
+        let duration = Data::DateTime(ExcelDateTime::new(1.5, ExcelDateTimeType::TimeDelta, false));
+        assert_eq!(render_cell(&duration, plain).0, "36:00:00");
+
+        assert_eq!(
+            render_cell(&Data::Float(0.1 + 0.2), plain),
+            ("0.3".to_string(), Some(CellValue::Number(0.1 + 0.2)))
+        );
+        assert_eq!(render_cell(&Data::Int(12), plain).0, "12");
+        assert_eq!(
+            render_cell(&Data::Bool(true), plain),
+            ("TRUE".to_string(), Some(CellValue::Bool(true)))
+        );
+        assert_eq!(
+            render_cell(&Data::Error(CellErrorType::NA), plain),
+            (String::new(), None)
+        );
+
+        let percent = NumberFormat {
+            decimals: Some(1),
+            percent: true,
+        };
+        assert_eq!(render_cell(&Data::Float(0.35), percent).0, "35.0%");
+        assert_eq!(format_number(-0.0000000000001, plain), "0");
+
+        for text in ["plain", "decimals:2", "percent", "percent:1"] {
+            assert_eq!(text.parse::<NumberFormat>().unwrap().to_string(), text);
+        }
+        assert!("decimals".parse::<NumberFormat>().is_err());
+    }
+
+    #[test]
+    fn test_typed_values_serialize_as_json_scalars() {
+        let values = vec![
+            CellValue::Number(4.5),
+            CellValue::Bool(false),
+            CellValue::from("2024-03-02"),
+        ];
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(json, r#"[4.5,false,"2024-03-02"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<CellValue>>(&json).unwrap(),
+            values
+        );
+
+        assert!(CellValue::Number(17.0).matches(&CellValue::from("17.0")));
+        assert!(!CellValue::Bool(true).matches(&CellValue::from("yes")));
+        assert!(CellValue::from("Dana").matches(&CellValue::from("dana")));
+        assert_eq!(CellValue::Number(17.0), "17");
+    }
+}
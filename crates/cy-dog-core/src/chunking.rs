@@ -0,0 +1,243 @@
+//! Splitting rows with long free-text cells (e.g. whole transcripts) into
+//! overlapping chunks that fit the embedding model's context.
+//!
+//! The short cells of the row are repeated in every chunk so each chunk still
+//! says who and what it is about; citations keep pointing at the parent row.
+
+use serde::{Deserialize, Serialize};
+
+/// Largest chunk, in estimated tokens; well within common embedding contexts
+pub const MAX_CHUNK_TOKENS: usize = 512;
+
+/// Tokens repeated at the start of the next chunk so sentences are not cut off
+pub const CHUNK_OVERLAP_TOKENS: usize = 64;
+
+/// Smallest room left for cell text before the short cells are left out of chunks
+const MIN_WINDOW_TOKENS: usize = 128;
+
+/// Where a chunk sits within the row it was split from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+    /// Column whose text was split (empty for paragraphs of notes)
+    pub column: String,
+    /// Position among the row's chunks (0-based)
+    pub index: usize,
+    /// Number of chunks the row was split into
+    pub count: usize,
+    /// Character offset of the chunk's text within the cell
+    pub offset: usize,
+}
+
+/// Rough token count: one per four characters, or one per word if that is more
+pub fn estimate_tokens(text: &str) -> usize {
+    tokens_of(text.chars().count(), text.split_whitespace().count())
+}
+
+fn tokens_of(chars: usize, words: usize) -> usize {
+    chars.div_ceil(4).max(words)
+}
+
+/// Content of a row as one or more chunks.
+///
+/// `prefix` is the citation text ("From file, Row 3: ") and `parts` are the
+/// embedded (header, value) pairs. Rows that fit are returned whole.
+pub fn chunk_row(prefix: &str, parts: &[(String, String)]) -> Vec<(String, Option<Chunk>)> {
+    let whole = format!("{}{}", prefix, join_parts(parts.iter()));
+    if estimate_tokens(&whole) <= MAX_CHUNK_TOKENS {
+        return vec![(whole, None)];
+    }
+
+    // Long cells are split; the others are context repeated in every chunk,
+    // unless they leave too little room for the text
+    let is_long = |value: &str| estimate_tokens(value) > MAX_CHUNK_TOKENS / 4;
+    let long: Vec<_> = parts.iter().filter(|(_, value)| is_long(value)).collect();
+    let label_of = |header: &str| estimate_tokens(&format!("{} {}", header, part_label(999, 999)));
+    let longest_label = long
+        .iter()
+        .map(|(header, _)| label_of(header))
+        .max()
+        .unwrap_or(0);
+    let mut context = join_parts(parts.iter().filter(|(_, value)| !is_long(value)));
+    if estimate_tokens(prefix) + estimate_tokens(&context) + longest_label + 2 + MIN_WINDOW_TOKENS
+        > MAX_CHUNK_TOKENS
+    {
+        context.clear();
+    }
+    let fixed = estimate_tokens(prefix) + estimate_tokens(&context);
+
+    let mut windows = Vec::new();
+    for (header, value) in long {
+        // Room left after the citation, the context, the "Header (part i/n):"
+        // label and the separators between them
+        let budget = MAX_CHUNK_TOKENS
+            .saturating_sub(fixed + label_of(header) + 2)
+            .max(1);
+        for (offset, text) in windows_of(value, budget) {
+            windows.push((header, offset, text));
+        }
+    }
+
+    // Many short cells: nothing to split, keep the row whole
+    if windows.is_empty() {
+        return vec![(whole, None)];
+    }
+
+    let count = windows.len();
+    windows
+        .into_iter()
+        .enumerate()
+        .map(|(index, (header, offset, text))| {
+            let part = format!("{} {} {}", header, part_label(index + 1, count), text);
+            let content = if context.is_empty() {
+                format!("{}{}", prefix, part)
+            } else {
+                format!("{}{}, {}", prefix, context, part)
+            };
+            let chunk = Chunk {
+                column: header.clone(),
+                index,
+                count,
+                offset,
+            };
+            (content, Some(chunk))
+        })
+        .collect()
+}
+
+/// Content of a free-text paragraph as one or more chunks, each starting with `prefix`
+pub fn chunk_text(prefix: &str, text: &str) -> Vec<(String, Option<Chunk>)> {
+    let whole = format!("{}{}", prefix, text);
+    if estimate_tokens(&whole) <= MAX_CHUNK_TOKENS {
+        return vec![(whole, None)];
+    }
+
+    let budget = MAX_CHUNK_TOKENS
+        .saturating_sub(estimate_tokens(prefix) + estimate_tokens(&part_label(999, 999)) + 1)
+        .max(1);
+    let windows = windows_of(text, budget);
+    let count = windows.len();
+    windows
+        .into_iter()
+        .enumerate()
+        .map(|(index, (offset, window))| {
+            let content = format!("{}{} {}", prefix, part_label(index + 1, count), window);
+            let chunk = Chunk {
+                column: String::new(),
+                index,
+                count,
+                offset,
+            };
+            (content, Some(chunk))
+        })
+        .collect()
+}
+
+/// "(part 2/5):"
+fn part_label(number: usize, count: usize) -> String {
+    format!("(part {}/{}):", number, count)
+}
+
+fn join_parts<'a>(parts: impl Iterator<Item = &'a (String, String)>) -> String {
+    parts
+        .map(|(header, value)| format!("{}: {}", header, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Overlapping windows of at most `budget` tokens, with their character offsets
+fn windows_of(text: &str, budget: usize) -> Vec<(usize, String)> {
+    // Words with their byte offsets in `text`
+    let words: Vec<(usize, &str)> = text
+        .split_whitespace()
+        .map(|word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+        .collect();
+    let overlap = CHUNK_OVERLAP_TOKENS.min(budget / 2);
+
+    let mut windows = Vec::new();
+    let mut start = 0;
+    while start < words.len() {
+        // Grow the window while the words joined by spaces fit the budget
+        let mut end = start;
+        let mut chars = 0;
+        while end < words.len() {
+            let with_word = chars + usize::from(end > start) + words[end].1.chars().count();
+            if end > start && tokens_of(with_word, end + 1 - start) > budget {
+                break;
+            }
+            chars = with_word;
+            end += 1;
+        }
+
+        let byte_offset = words[start].0;
+        let text_window: Vec<&str> = words[start..end].iter().map(|(_, word)| *word).collect();
+        windows.push((text[..byte_offset].chars().count(), text_window.join(" ")));
+
+        if end == words.len() {
+            break;
+        }
+
+        // Step back far enough to repeat about `overlap` tokens, always moving forward
+        let mut next = end;
+        let mut repeated = 0;
+        while next > start + 1 && repeated < overlap {
+            next -= 1;
+            repeated += estimate_tokens(words[next].1);
+        }
+        start = next;
+    }
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_cell_is_split_with_overlap_and_context() {
+        let transcript: Vec<String> = (0..1200).map(|i| format!("w{}", i)).collect();
+        let parts = vec![
+            ("Name".to_string(), "Alice".to_string()),
+            ("Transcript".to_string(), transcript.join(" ")),
+        ];
+
+        let chunks = chunk_row("From calls.csv, Row 3: ", &parts);
+        assert!(chunks.len() >= 3);
+        for (content, chunk) in &chunks {
+            assert!(content.starts_with("From calls.csv, Row 3: Name: Alice, Transcript (part "));
+            assert!(estimate_tokens(content) <= MAX_CHUNK_TOKENS);
+            assert_eq!(chunk.as_ref().unwrap().count, chunks.len());
+        }
+
+        // Consecutive chunks overlap and every word is covered
+        let second = chunks[1].1.as_ref().unwrap();
+        let first_end = chunks[0].0.rsplit(' ').next().unwrap();
+        assert!(chunks[1].0.contains(&format!(" {} ", first_end)));
+        assert!(second.offset > 0);
+        assert!(chunks.last().unwrap().0.ends_with("w1199"));
+
+        let notes: Vec<String> = (0..800).map(|i| format!("n{}", i)).collect();
+        let paragraphs = chunk_text("From acme.md, Paragraph 4: ", &notes.join(" "));
+        assert!(paragraphs.len() >= 2);
+        for (content, _) in &paragraphs {
+            assert!(content.starts_with("From acme.md, Paragraph 4: (part "));
+            assert!(estimate_tokens(content) <= MAX_CHUNK_TOKENS);
+        }
+
+        // A long file name leaves less room for the text, never more than the limit
+        let name = "customer-discovery-interview-transcript-".repeat(40);
+        let prefix = format!("From {}.csv, Row 3: ", name);
+        let chunks = chunk_row(&prefix, &parts);
+        assert!(chunks.len() >= 3);
+        for (content, _) in &chunks {
+            assert!(content.starts_with(&prefix));
+            assert!(estimate_tokens(content) <= MAX_CHUNK_TOKENS);
+        }
+        assert!(chunks.last().unwrap().0.ends_with("w1199"));
+
+        let short = chunk_row("From a.csv, Row 1: ", &parts[..1]);
+        assert_eq!(
+            short,
+            vec![("From a.csv, Row 1: Name: Alice".to_string(), None)]
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Named collections: independent datasets, each with its own index file,
+//! source folders, embedding model and chat model.
+//!
+//! The list of collections and the active one are kept in a small manifest
+//! next to the index files so the desktop app and the CLI agree on them.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::provider::EMBEDDING_MODEL;
+use crate::schema::DatasetSchema;
+use crate::store::{default_data_dir, IndexStore};
+
+/// Id of the collection created on first run (uses the pre-collections index file)
+pub const DEFAULT_COLLECTION_ID: &str = "default";
+
+/// Manifest listing every collection
+const MANIFEST_FILE_NAME: &str = "collections.json";
+
+/// Directory holding the index of every non-default collection
+const COLLECTIONS_DIR: &str = "collections";
+
+/// Index file name; the default collection's sits directly in the data
+/// directory, where it was before collections existed
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// A named dataset and its settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionInfo {
+    /// Stable identifier derived from the name at creation
+    pub id: String,
+    pub name: String,
+    /// Folders the index was built from
+    #[serde(default)]
+    pub folders: Vec<String>,
+    /// Individual files added outside those folders
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Rows in the index when it was last built
+    #[serde(default)]
+    pub document_count: usize,
+    /// Model the index is (or will be) embedded with
+    pub embedding_model: String,
+    /// Chat model last used with this collection
+    #[serde(default)]
+    pub chat_model: Option<String>,
+    /// How each column is used when the collection is indexed
+    #[serde(default)]
+    pub schema: DatasetSchema,
+    /// Seconds since the Unix epoch
+    pub created_at: u64,
+}
+
+impl CollectionInfo {
+    /// A collection with nothing ingested yet
+    pub fn new(id: impl Into<String>, name: impl Into<String>, embedding_model: &str) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            folders: Vec::new(),
+            files: Vec::new(),
+            document_count: 0,
+            embedding_model: embedding_model.to_string(),
+            chat_model: None,
+            schema: DatasetSchema::default(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Manifest {
+    active: String,
+    collections: Vec<CollectionInfo>,
+}
+
+/// Reads and writes the collection manifest and locates each collection's index
+#[derive(Debug)]
+pub struct CollectionStore {
+    dir: PathBuf,
+    /// Serialises read-modify-write cycles on the manifest
+    lock: Mutex<()>,
+}
+
+impl CollectionStore {
+    /// Store rooted at a specific data directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Store in the default data directory, shared by the desktop app and CLI
+    pub fn default_location() -> Self {
+        Self::new(default_data_dir())
+    }
+
+    /// The data directory the manifest and indexes are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Every collection, in creation order
+    pub fn list(&self) -> Result<Vec<CollectionInfo>> {
+        Ok(self.read()?.collections)
+    }
+
+    /// The collection commands act on by default
+    pub fn active(&self) -> Result<CollectionInfo> {
+        let manifest = self.read()?;
+        find(&manifest, &manifest.active).cloned()
+    }
+
+    pub fn get(&self, id: &str) -> Result<CollectionInfo> {
+        find(&self.read()?, id).cloned()
+    }
+
+    /// Where the index of a collection is persisted
+    pub fn index_store(&self, id: &str) -> IndexStore {
+        if id == DEFAULT_COLLECTION_ID {
+            IndexStore::new(self.dir.join(INDEX_FILE_NAME))
+        } else {
+            IndexStore::new(
+                self.dir
+                    .join(COLLECTIONS_DIR)
+                    .join(id)
+                    .join(INDEX_FILE_NAME),
+            )
+        }
+    }
+
+    /// Add an empty collection (does not make it active)
+    pub fn create(&self, name: &str, embedding_model: &str) -> Result<CollectionInfo> {
+        let name = validate_name(name)?;
+        self.update(|manifest| {
+            ensure_unique_name(manifest, &name, None)?;
+
+            let base = slugify(&name);
+            let mut id = base.clone();
+            let mut suffix = 2;
+            while manifest.collections.iter().any(|c| c.id == id) {
+                id = format!("{}-{}", base, suffix);
+                suffix += 1;
+            }
+
+            let info = CollectionInfo::new(id, name.clone(), embedding_model);
+            manifest.collections.push(info.clone());
+            Ok(info)
+        })
+    }
+
+    /// Make a collection the active one
+    pub fn set_active(&self, id: &str) -> Result<CollectionInfo> {
+        self.update(|manifest| {
+            let info = find(manifest, id)?.clone();
+            manifest.active = info.id.clone();
+            Ok(info)
+        })
+    }
+
+    /// Change the display name (the id stays the same)
+    pub fn rename(&self, id: &str, name: &str) -> Result<CollectionInfo> {
+        let name = validate_name(name)?;
+        self.update(|manifest| {
+            ensure_unique_name(manifest, &name, Some(id))?;
+            let info = find_mut(manifest, id)?;
+            info.name = name.clone();
+            Ok(info.clone())
+        })
+    }
+
+    /// Apply a change to one collection's settings and save it
+    pub fn modify(
+        &self,
+        id: &str,
+        change: impl FnOnce(&mut CollectionInfo),
+    ) -> Result<CollectionInfo> {
+        self.update(|manifest| {
+            let info = find_mut(manifest, id)?;
+            change(info);
+            Ok(info.clone())
+        })
+    }
+
+    /// Delete a collection and its index, returning the collection now active.
+    /// The last remaining collection cannot be deleted.
+    pub fn delete(&self, id: &str) -> Result<CollectionInfo> {
+        let active = self.update(|manifest| {
+            find(manifest, id)?;
+            if manifest.collections.len() == 1 {
+                return Err(Error::InvalidCollection(
+                    "The last collection cannot be deleted".to_string(),
+                ));
+            }
+
+            manifest.collections.retain(|c| c.id != id);
+            if manifest.active == id {
+                manifest.active = manifest.collections[0].id.clone();
+            }
+            find(manifest, &manifest.active).cloned()
+        })?;
+
+        self.index_store(id).clear()?;
+        if id != DEFAULT_COLLECTION_ID {
+            let dir = self.dir.join(COLLECTIONS_DIR).join(id);
+            if dir.exists() {
+                fs::remove_dir_all(&dir).map_err(|source| io_error(&dir, source))?;
+            }
+        }
+        Ok(active)
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Read the manifest, or a single default collection if there is none yet
+    fn read(&self) -> Result<Manifest> {
+        let path = self.manifest_path();
+        if !path.exists() {
+            return Ok(Manifest {
+                active: DEFAULT_COLLECTION_ID.to_string(),
+                collections: vec![CollectionInfo::new(
+                    DEFAULT_COLLECTION_ID,
+                    "Default",
+                    EMBEDDING_MODEL,
+                )],
+            });
+        }
+
+        let bytes = fs::read(&path).map_err(|source| io_error(&path, source))?;
+        serde_json::from_slice(&bytes).map_err(|e| Error::CorruptIndex {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    fn update<T>(&self, change: impl FnOnce(&mut Manifest) -> Result<T>) -> Result<T> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut manifest = self.read()?;
+        let result = change(&mut manifest)?;
+
+        let path = self.manifest_path();
+        fs::create_dir_all(&self.dir).map_err(|source| io_error(&self.dir, source))?;
+        let json = serde_json::to_vec_pretty(&manifest).map_err(|e| Error::Io {
+            path: path.display().to_string(),
+            source: e.into(),
+        })?;
+
+        // Same write-then-rename as the index so a crash never truncates the manifest
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).map_err(|source| io_error(&tmp_path, source))?;
+        fs::rename(&tmp_path, &path).map_err(|source| io_error(&path, source))?;
+
+        Ok(result)
+    }
+}
+
+fn find<'a>(manifest: &'a Manifest, id: &str) -> Result<&'a CollectionInfo> {
+    manifest
+        .collections
+        .iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| Error::CollectionNotFound(id.to_string()))
+}
+
+fn find_mut<'a>(manifest: &'a mut Manifest, id: &str) -> Result<&'a mut CollectionInfo> {
+    manifest
+        .collections
+        .iter_mut()
+        .find(|c| c.id == id)
+        .ok_or_else(|| Error::CollectionNotFound(id.to_string()))
+}
+
+fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::InvalidCollection(
+            "Collection name cannot be empty".to_string(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn ensure_unique_name(manifest: &Manifest, name: &str, except_id: Option<&str>) -> Result<()> {
+    let taken = manifest
+        .collections
+        .iter()
+        .any(|c| Some(c.id.as_str()) != except_id && c.name.eq_ignore_ascii_case(name));
+    if taken {
+        return Err(Error::InvalidCollection(format!(
+            "A collection named \"{}\" already exists",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Lowercase ASCII id safe to use as a directory name
+fn slugify(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "collection".to_string()
+    } else {
+        slug
+    }
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> Error {
+    Error::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_rename_switch_and_delete() {
+        let dir = std::env::temp_dir().join(format!("cy-dog-collections-{}", std::process::id()));
+        let store = CollectionStore::new(&dir);
+
+        assert_eq!(store.active().unwrap().id, DEFAULT_COLLECTION_ID);
+
+        let product = store.create("Product A (EU)", "nomic-embed-text").unwrap();
+        assert_eq!(product.id, "product-a-eu");
+        assert!(store.create("product a (eu)", "nomic-embed-text").is_err());
+
+        store.rename(&product.id, "Product A").unwrap();
+        store.set_active(&product.id).unwrap();
+        assert_eq!(store.active().unwrap().name, "Product A");
+
+        let active = store.delete(&product.id).unwrap();
+        assert_eq!(active.id, DEFAULT_COLLECTION_ID);
+        assert!(store.delete(DEFAULT_COLLECTION_ID).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+//! How a CSV file is written: its character encoding, field delimiter and
+//! quote character.
+//!
+//! Excel exports are often Windows-1252 or UTF-16 rather than UTF-8, and
+//! European locales separate fields with semicolons. Both are detected from
+//! the file's bytes so the rows are read correctly.
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Delimiters tried when sniffing, in order of preference on a tie
+const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+/// Lines looked at when sniffing the delimiter and quote
+const SNIFF_LINES: usize = 20;
+
+/// How a CSV file was read
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvDialect {
+    /// Character encoding, e.g. "UTF-8" or "windows-1252" (which covers Latin-1)
+    pub encoding: String,
+    /// Field delimiter
+    pub delimiter: char,
+    /// Quote character
+    pub quote: char,
+}
+
+impl CsvDialect {
+    /// Whether this is plain comma-separated UTF-8 with double quotes
+    pub fn is_default(&self) -> bool {
+        self.encoding == UTF_8.name() && self.delimiter == ',' && self.quote == '"'
+    }
+}
+
+impl fmt::Display for CsvDialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let delimiter = match self.delimiter {
+            ',' => "comma",
+            ';' => "semicolon",
+            '\t' => "tab",
+            '|' => "pipe",
+            _ => "custom",
+        };
+        write!(f, "{}, {}-separated", self.encoding, delimiter)?;
+        if self.quote != '"' {
+            write!(f, ", {} quotes", self.quote)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode a CSV file's bytes and detect its dialect
+pub fn read_csv_text(bytes: &[u8]) -> (String, CsvDialect) {
+    let (text, encoding) = decode_text(bytes);
+    let quote = sniff_quote(&text);
+    let delimiter = sniff_delimiter(&text, quote);
+
+    let dialect = CsvDialect {
+        encoding: encoding.name().to_string(),
+        delimiter,
+        quote,
+    };
+    (text, dialect)
+}
+
+/// Decode a text file's bytes in their detected encoding (UTF-8, UTF-16 or a
+/// legacy code page such as Windows-1252)
+pub fn decode_text(bytes: &[u8]) -> (String, &'static Encoding) {
+    let encoding = detect_encoding(bytes);
+    let (text, _, _) = encoding.decode(bytes);
+    (text.into_owned(), encoding)
+}
+
+/// Byte order mark, else valid UTF-8, else UTF-16 by its zero bytes, else a
+/// statistical guess (typically Windows-1252 for Western European text)
+fn detect_encoding(bytes: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return UTF_8;
+    }
+
+    // ASCII text in UTF-16 has a zero byte in every other position
+    let sample = &bytes[..bytes.len().min(4096)];
+    let zeros_at = |parity: usize| {
+        sample
+            .iter()
+            .skip(parity)
+            .step_by(2)
+            .filter(|&&b| b == 0)
+            .count()
+    };
+    let pairs = sample.len() / 2;
+    if pairs > 0 && zeros_at(1) * 5 >= pairs * 2 {
+        return UTF_16LE;
+    }
+    if pairs > 0 && zeros_at(0) * 5 >= pairs * 2 {
+        return UTF_16BE;
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(None, false)
+}
+
+/// Single quotes only when fields are wrapped in them and never in double quotes
+fn sniff_quote(text: &str) -> char {
+    let wrapped = |quote: char| {
+        sample_lines(text)
+            .flat_map(|line| line.split(DELIMITERS))
+            .filter(|field| {
+                let field = field.trim();
+                field.len() >= 2 && field.starts_with(quote) && field.ends_with(quote)
+            })
+            .count()
+    };
+
+    if wrapped('"') == 0 && wrapped('\'') > 0 {
+        '\''
+    } else {
+        '"'
+    }
+}
+
+/// The delimiter found the same number of times on the most lines
+fn sniff_delimiter(text: &str, quote: char) -> char {
+    let lines: Vec<&str> = sample_lines(text).collect();
+    let mut best = (',', (0, 0));
+
+    for delimiter in DELIMITERS {
+        let counts: Vec<usize> = lines
+            .iter()
+            .map(|line| count_outside_quotes(line, delimiter, quote))
+            .collect();
+
+        // Most common non-zero count per line, and on how many lines it occurs
+        let mut score = (0, 0);
+        for &count in counts.iter().filter(|&&count| count > 0) {
+            let lines_with_count = counts.iter().filter(|&&c| c == count).count();
+            score = score.max((lines_with_count, count));
+        }
+
+        if score > best.1 {
+            best = (delimiter, score);
+        }
+    }
+
+    best.0
+}
+
+fn sample_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(SNIFF_LINES)
+}
+
+fn count_outside_quotes(line: &str, delimiter: char, quote: char) -> usize {
+    let mut quoted = false;
+    let mut count = 0;
+    for c in line.chars() {
+        if c == quote {
+            quoted = !quoted;
+        } else if c == delimiter && !quoted {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_1252_semicolon_export() {
+        let bytes = b"Name;Comment;Score\r\nZo\xe9;Le caf\xe9 est trop cher;4,5\r\nAnna;\"Pr\xe4sentation; zu lang\";3,0\r\n";
+
+        let (text, dialect) = read_csv_text(bytes);
+        assert!(text.contains("Le café est trop cher"));
+        assert_eq!(dialect.encoding, "windows-1252");
+        assert_eq!(dialect.delimiter, ';');
+        assert_eq!(dialect.quote, '"');
+        assert_eq!(dialect.to_string(), "windows-1252, semicolon-separated");
+    }
+
+    #[test]
+    fn test_utf16_tab_and_pipe_files() {
+        let mut bytes = vec![0xff, 0xfe];
+        for unit in "Name\tNote\nAlice\tCafé\n".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        let (text, dialect) = read_csv_text(&bytes);
+        assert_eq!(text, "Name\tNote\nAlice\tCafé\n");
+        assert_eq!(dialect.encoding, "UTF-16LE");
+        assert_eq!(dialect.delimiter, '\t');
+
+        let (_, dialect) = read_csv_text(b"id|answer\n1|'Too slow'\n2|'Fine, mostly'\n");
+        assert_eq!(dialect.delimiter, '|');
+        assert_eq!(dialect.quote, '\'');
+        assert!(!dialect.is_default());
+
+        let (_, dialect) = read_csv_text(b"Name,Note\nAlice,\"Slow; very\"\n");
+        assert!(dialect.is_default());
+    }
+}
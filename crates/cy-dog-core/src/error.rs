@@ -0,0 +1,137 @@
+use thiserror::Error;
+
+/// Errors produced by the Cy Dog core library
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The data folder passed to the loader does not exist
+    #[error("Directory does not exist: {0}")]
+    DirectoryNotFound(String),
+
+    /// The data folder passed to the loader is a file
+    #[error("Path is not a directory: {0}")]
+    NotADirectory(String),
+
+    /// Reading a file or directory failed
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A spreadsheet could not be parsed (row is 1-indexed when known)
+    #[error("Failed to parse {file}{}: {message}", row_suffix(.row))]
+    Parse {
+        file: String,
+        row: Option<usize>,
+        message: String,
+    },
+
+    /// There were no documents to build an index from
+    #[error("No documents to embed")]
+    NoDocuments,
+
+    /// The embedding model failed or was unreachable
+    #[error("Failed to build embeddings with {model}: {source}")]
+    Embedding {
+        model: String,
+        #[source]
+        source: rig::embeddings::EmbeddingError,
+    },
+
+    /// The persisted index could not be decoded
+    #[error("Saved index at {path} is unreadable: {message}")]
+    CorruptIndex { path: String, message: String },
+
+    /// The persisted index was built with a different embedding model
+    #[error("Index was built with embedding model {indexed} but {configured} is configured")]
+    EmbeddingModelMismatch { indexed: String, configured: String },
+
+    /// The chat model failed to produce an answer
+    #[error("Failed to generate response from {model}: {source}")]
+    Completion {
+        model: String,
+        #[source]
+        source: rig::completion::PromptError,
+    },
+
+    /// The provider's HTTP API could not be reached
+    #[error("Failed to connect to {url}: {source}")]
+    Connection {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// The provider answered with a non-success status or an unexpected body
+    #[error("{url} returned an invalid response: {message}")]
+    InvalidResponse { url: String, message: String },
+
+    /// The index holds no rows from the given file
+    #[error("No indexed rows come from {0}")]
+    SourceNotFound(String),
+
+    /// Two different files with the same name would be indexed together;
+    /// citations only name the file, so their rows could not be told apart
+    #[error("{existing} and {added} are both named {name}; rename one of them before adding it")]
+    DuplicateSourceName {
+        name: String,
+        existing: String,
+        added: String,
+    },
+
+    /// No collection has the given id
+    #[error("Collection not found: {0}")]
+    CollectionNotFound(String),
+
+    /// A collection could not be created, renamed or deleted as asked
+    #[error("{0}")]
+    InvalidCollection(String),
+
+    /// A setting such as a column role was given an unknown value
+    #[error("{0}")]
+    InvalidSetting(String),
+}
+
+impl Error {
+    /// Whether the provider could not be reached at all (server down, wrong URL)
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            Self::Connection { source, .. } => source.is_connect() || source.is_timeout(),
+            Self::Embedding { .. } | Self::Completion { .. } => {
+                // rig wraps the reqwest error, so look for it down the source chain;
+                // its Ollama client keeps only the message ("error sending request ...")
+                let mut cause = std::error::Error::source(self);
+                while let Some(error) = cause {
+                    if let Some(http) = error.downcast_ref::<reqwest::Error>() {
+                        return http.is_connect() || http.is_timeout();
+                    }
+                    if error.to_string().contains("error sending request") {
+                        return true;
+                    }
+                    cause = error.source();
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the provider rejected the request because the model is not installed
+    pub fn is_model_missing(&self) -> bool {
+        match self {
+            // Ollama answers 404 with "model \"x\" not found, try pulling it first"
+            Self::Embedding { .. } | Self::Completion { .. } | Self::InvalidResponse { .. } => {
+                let message = self.to_string().to_lowercase();
+                message.contains("not found") || message.contains("404")
+            }
+            _ => false,
+        }
+    }
+}
+
+fn row_suffix(row: &Option<usize>) -> String {
+    row.map(|r| format!(" at row {}", r)).unwrap_or_default()
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
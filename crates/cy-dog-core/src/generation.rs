@@ -0,0 +1,87 @@
+use futures::stream::BoxStream;
+
+use crate::error::Result;
+use crate::index::EmbeddableDocument;
+use crate::provider::Provider;
+
+/// Generate a RAG response using the selected chat model
+pub async fn generate_rag_response(
+    query: &str,
+    context_docs: Vec<EmbeddableDocument>,
+    provider: &Provider,
+    model_name: &str,
+) -> Result<(String, Vec<String>)> {
+    let (full_prompt, sources) = build_rag_prompt(query, &context_docs);
+    
+    // Generate response with our specialized preamble
+    let response = provider
+        .prompt(model_name, CUSTOMER_DISCOVERY_PREAMBLE, &full_prompt)
+        .await?;
+    
+    Ok((response, sources))
+}
+
+/// Like `generate_rag_response`, but yields the answer as it is generated
+pub async fn stream_rag_response(
+    query: &str,
+    context_docs: Vec<EmbeddableDocument>,
+    provider: &Provider,
+    model_name: &str,
+) -> Result<(BoxStream<'static, Result<String>>, Vec<String>)> {
+    let (full_prompt, sources) = build_rag_prompt(query, &context_docs);
+    
+    let tokens = provider
+        .stream_prompt(model_name, CUSTOMER_DISCOVERY_PREAMBLE, &full_prompt)
+        .await?;
+    
+    Ok((tokens, sources))
+}
+
+/// Build the grounded prompt for a question and the source labels of its context
+fn build_rag_prompt(query: &str, context_docs: &[EmbeddableDocument]) -> (String, Vec<String>) {
+    // Build context from retrieved documents
+    let context = context_docs
+        .iter()
+        .map(|doc| doc.content.clone())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    
+    // Extract sources for attribution (chunks of one row are cited once)
+    let mut sources: Vec<String> = Vec::with_capacity(context_docs.len());
+    for citation in context_docs.iter().map(EmbeddableDocument::citation) {
+        if !sources.contains(&citation) {
+            sources.push(citation);
+        }
+    }
+    
+    // Construct the prompt with context
+    let full_prompt = format!(
+        "Based on the following interview data from our customer discovery research:\n\n\
+        ---BEGIN DATA---\n{}\n---END DATA---\n\n\
+        Question: {}\n\n\
+        Remember: Answer ONLY based on the data provided above. If the information is not in the data, say so.",
+        context,
+        query
+    );
+    
+    (full_prompt, sources)
+}
+
+/// The system preamble for the Customer Discovery Specialist persona
+const CUSTOMER_DISCOVERY_PREAMBLE: &str = r#"You are a Customer Discovery Specialist for Inis Informatics, an expert consultant analyzing interview notes and customer research data.
+
+Your role:
+- Analyze customer interview data to extract actionable insights
+- Identify patterns, pain points, and opportunities from the research
+- Provide concise, evidence-based answers grounded ONLY in the provided data
+- Cite specific rows/sources when making claims
+- Be direct and business-focused in your responses
+
+Important guidelines:
+- NEVER make up information not present in the data
+- If asked about something not in the data, clearly state that the information is not available
+- Focus on patterns across multiple data points when possible
+- Highlight direct quotes when relevant
+- Keep responses concise and actionable
+
+You are reviewing spreadsheet data from customer discovery interviews. Each piece of context includes the source file and row number for reference."#;
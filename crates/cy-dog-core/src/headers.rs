@@ -0,0 +1,257 @@
+//! Finding the header of messy spreadsheets.
+//!
+//! Interview templates often start with a title block and blank rows, and
+//! group columns under merged cells, giving a two-row header. Header names
+//! are made unique, and blank ones are named after their column letter so
+//! their values are not lost.
+
+/// Rows looked at when searching for the header
+const SCAN_ROWS: usize = 20;
+
+/// Longest cell, in words, still taken as a column name
+const MAX_HEADER_WORDS: usize = 6;
+
+/// Separator between the two rows of a grouped header
+const GROUP_SEPARATOR: &str = " / ";
+
+/// The header of a sheet
+#[derive(Debug, PartialEq)]
+pub struct Header {
+    /// Column names, unique and non-empty
+    pub names: Vec<String>,
+    /// Index of the first data row (the row after the header)
+    pub data_start: usize,
+}
+
+/// Find the header among the first rows: the first row whose cells are
+/// mostly filled with text, combined with a second header row below it when
+/// its columns are grouped under merged cells. `first_column` is the 0-based
+/// index of the rows' first column, used to name blank header cells.
+pub fn detect_header(rows: &[Vec<String>], first_column: usize) -> Option<Header> {
+    let scanned = &rows[..rows.len().min(SCAN_ROWS)];
+    let width = scanned.iter().map(|row| filled(row)).max().unwrap_or(0);
+    if width == 0 {
+        return None;
+    }
+
+    let is_header = |row: &Vec<String>| {
+        let cells = filled(row);
+        let text = row.iter().filter(|cell| is_label(cell)).count();
+        cells >= width.min(2) && cells * 2 >= width && text * 2 >= cells
+    };
+    let index = scanned
+        .iter()
+        .position(is_header)
+        .or_else(|| scanned.iter().position(|row| filled(row) > 0))?;
+
+    let top = &rows[index];
+    let below = &rows[rows.len().min(index + 2)..rows.len().min(index + 2 + SCAN_ROWS)];
+    let (names, data_start) = match rows
+        .get(index + 1)
+        .filter(|next| is_sub_header(top, next, below))
+    {
+        Some(sub) => (group_names(top, sub), index + 2),
+        None => (top.clone(), index + 1),
+    };
+
+    Some(Header {
+        names: unique_names(names, first_column),
+        data_start,
+    })
+}
+
+/// Make names unique ("Notes", "Notes (2)") and name blank ones after their
+/// column ("Column C")
+pub fn unique_names(names: Vec<String>, first_column: usize) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(names.len());
+
+    for (i, name) in names.into_iter().enumerate() {
+        let name = name.trim();
+        let base = if name.is_empty() {
+            format!("Column {}", column_letter(first_column + i))
+        } else {
+            name.to_string()
+        };
+
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while unique.contains(&candidate) {
+            candidate = format!("{} ({})", base, n);
+            n += 1;
+        }
+        unique.push(candidate);
+    }
+
+    unique
+}
+
+/// Whether `next` is the second row of a grouped header under `top`: it
+/// labels columns left blank in `top` only inside merged groups (blank cells
+/// following a named one), leaves columns that `top` names on their own
+/// blank, and its labels are unlike the values `below` them. A data row with
+/// a blank cell would otherwise pass for one and be lost.
+fn is_sub_header(top: &[String], next: &[String], below: &[Vec<String>]) -> bool {
+    let columns = top.len().max(next.len());
+    let labelled: Vec<usize> = (0..columns)
+        .filter(|&i| !cell(next, i).is_empty())
+        .collect();
+
+    // Walking left from a child, the merged parent comes before a column
+    // that is blank in both rows
+    let in_group = |i: usize| {
+        (0..i)
+            .rev()
+            .find(|&j| !cell(top, j).is_empty() || cell(next, j).is_empty())
+            .is_some_and(|j| !cell(top, j).is_empty())
+    };
+    let gaps: Vec<usize> = labelled
+        .iter()
+        .copied()
+        .filter(|&i| cell(top, i).is_empty())
+        .collect();
+    let leaves_blank = (0..columns).any(|i| !cell(top, i).is_empty() && cell(next, i).is_empty());
+    let all_labels = labelled.iter().all(|&i| is_label(cell(next, i)));
+
+    // Below a header the columns hold answers (long text, numbers); below a
+    // data row they hold more values like it
+    let holds_answers = |i: usize| {
+        below.iter().any(|row| {
+            let value = cell(row, i);
+            !value.is_empty() && !is_label(value)
+        })
+    };
+    let unlike_below = labelled.iter().filter(|&&i| holds_answers(i)).count() * 2 >= labelled.len();
+
+    !gaps.is_empty()
+        && gaps.iter().all(|&i| in_group(i))
+        && leaves_blank
+        && all_labels
+        && labelled.len() >= 2
+        && unlike_below
+}
+
+/// "Parent / Child" names, carrying each merged parent over the blank cells to its right
+fn group_names(top: &[String], sub: &[String]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut parent = String::new();
+
+    for i in 0..top.len().max(sub.len()) {
+        let (top, child) = (cell(top, i).to_string(), cell(sub, i).to_string());
+        if !top.is_empty() {
+            parent = top;
+        } else if child.is_empty() {
+            // A blank column ends the merged group
+            parent.clear();
+        }
+
+        names.push(match (parent.is_empty(), child.is_empty()) {
+            (false, false) => format!("{}{}{}", parent, GROUP_SEPARATOR, child),
+            (false, true) => parent.clone(),
+            (true, _) => child,
+        });
+    }
+
+    names
+}
+
+/// Trimmed text of a cell; rows may be shorter than the header
+fn cell(row: &[String], i: usize) -> &str {
+    row.get(i).map_or("", |cell| cell.trim())
+}
+
+/// Number of non-empty cells
+fn filled(row: &[String]) -> usize {
+    row.iter().filter(|cell| !cell.trim().is_empty()).count()
+}
+
+/// A short, non-numeric cell, as column names are
+fn is_label(cell: &str) -> bool {
+    let cell = cell.trim();
+    !cell.is_empty()
+        && cell.parse::<f64>().is_err()
+        && cell.split_whitespace().count() <= MAX_HEADER_WORDS
+}
+
+/// 0 -> "A", 25 -> "Z", 26 -> "AA"
+fn column_letter(index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        letters.push((b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+    letters.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_header_below_title_block_with_grouped_columns() {
+        let sheet = rows(&[
+            &["Customer interviews, Q1 2024", "", "", "", ""],
+            &["", "", "", "", ""],
+            &["Name", "Feedback", "", "", "Date"],
+            &["", "Pain", "Gain", "", ""],
+            &[
+                "Alice",
+                "Scheduling shifts takes hours every single week",
+                "Saves time",
+                "Quote here",
+                "2024-03-02",
+            ],
+        ]);
+
+        assert_eq!(
+            detect_header(&sheet, 0),
+            Some(Header {
+                names: vec![
+                    "Name".to_string(),
+                    "Feedback / Pain".to_string(),
+                    "Feedback / Gain".to_string(),
+                    "Column D".to_string(),
+                    "Date".to_string(),
+                ],
+                data_start: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_sparse_first_data_row_is_not_a_second_header() {
+        let sheet = rows(&[
+            &["Name", "", "Notes"],
+            &["Alice", "Nurse", ""],
+            &[
+                "Bob",
+                "Pharmacist",
+                "Inventory counts are still done on paper",
+            ],
+        ]);
+
+        let header = detect_header(&sheet, 0).unwrap();
+        assert_eq!(header.names, vec!["Name", "Column B", "Notes"]);
+        assert_eq!(header.data_start, 1);
+    }
+
+    #[test]
+    fn test_single_header_row_with_blank_and_repeated_names() {
+        let sheet = rows(&[
+            &["Name", "Notes", "", "Notes"],
+            &["Alice", "Too slow", "x", "Follow up"],
+        ]);
+
+        let header = detect_header(&sheet, 1).unwrap();
+        assert_eq!(header.names, vec!["Name", "Notes", "Column D", "Notes (2)"]);
+        assert_eq!(header.data_start, 1);
+        assert_eq!(column_letter(27), "AB");
+    }
+}
@@ -0,0 +1,253 @@
+use rig::embeddings::EmbeddingModel;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::cells::CellValue;
+use crate::chunking::Chunk;
+use crate::error::{Error, Result};
+use crate::loader::{CsvDocument, Location};
+use crate::provider::{Provider, ProviderEmbeddingModel};
+
+/// A document as stored in the index and returned from searches
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EmbeddableDocument {
+    /// Unique identifier
+    pub id: String,
+    /// The content to embed
+    pub content: String,
+    /// Source file for attribution
+    pub source_file: String,
+    /// Row number for attribution
+    pub row_number: usize,
+    /// Filterable values that are not embedded (see `ColumnRole`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, CellValue>,
+    /// Position within its row when a long row was split
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<Chunk>,
+    /// Section of a notes document; `None` for spreadsheet rows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+}
+
+impl EmbeddableDocument {
+    /// "file, Row N" (or "file, Section, Paragraph N" for notes,
+    /// "file @ HH:MM:SS (Speaker: X)" for transcripts and "file, Page N" for
+    /// PDFs) as shown in answers; every chunk of a row cites the row
+    pub fn citation(&self) -> String {
+        match &self.location {
+            None => format!("{}, Row {}", self.source_file, self.row_number),
+            Some(Location::Paragraph { section: Some(section) }) => {
+                format!("{}, {}, Paragraph {}", self.source_file, section, self.row_number)
+            }
+            Some(Location::Paragraph { section: None }) => {
+                format!("{}, Paragraph {}", self.source_file, self.row_number)
+            }
+            Some(Location::Transcript { start, speaker: Some(speaker) }) => {
+                format!("{} @ {} (Speaker: {})", self.source_file, start, speaker)
+            }
+            Some(Location::Transcript { start, speaker: None }) => {
+                format!("{} @ {}", self.source_file, start)
+            }
+            Some(Location::Page) => format!("{}, Page {}", self.source_file, self.row_number),
+        }
+    }
+    
+    /// Whether this row came from `file_name` (Excel rows are labelled "file (Sheet)")
+    pub fn is_from_file(&self, file_name: &str) -> bool {
+        match self.source_file.strip_prefix(file_name) {
+            Some(rest) => rest.is_empty() || (rest.starts_with(" (") && rest.ends_with(')')),
+            None => false,
+        }
+    }
+}
+
+impl From<CsvDocument> for EmbeddableDocument {
+    fn from(doc: CsvDocument) -> Self {
+        Self {
+            id: doc.id,
+            content: doc.content,
+            source_file: doc.source_file,
+            row_number: doc.row_number,
+            metadata: doc.metadata,
+            chunk: doc.chunk,
+            location: doc.location,
+        }
+    }
+}
+
+/// A document together with its embedding vector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedDocument {
+    pub document: EmbeddableDocument,
+    pub embedding: Vec<f64>,
+}
+
+/// Embed documents with the provider's embedding model, in batches it accepts
+pub async fn embed_documents(
+    documents: Vec<CsvDocument>,
+    provider: &Provider,
+) -> Result<Vec<IndexedDocument>> {
+    let embedding_model = provider.embedding_model();
+    let embedding_model_name = &provider.config().embedding_model;
+    
+    // Convert to embeddable documents
+    let embeddable_docs: Vec<EmbeddableDocument> = documents
+        .into_iter()
+        .map(EmbeddableDocument::from)
+        .collect();
+    
+    if embeddable_docs.is_empty() {
+        return Err(Error::NoDocuments);
+    }
+    
+    // Build embeddings in batches the provider accepts
+    let mut indexed = Vec::with_capacity(embeddable_docs.len());
+    for batch in embeddable_docs.chunks(ProviderEmbeddingModel::MAX_DOCUMENTS) {
+        let texts: Vec<String> = batch.iter().map(|doc| doc.content.clone()).collect();
+        let embeddings = embedding_model
+            .embed_texts(texts)
+            .await
+            .map_err(|source| Error::Embedding {
+                model: embedding_model_name.clone(),
+                source,
+            })?;
+        
+        indexed.extend(batch.iter().cloned().zip(embeddings).map(|(document, embedding)| {
+            IndexedDocument {
+                document,
+                embedding: embedding.vec,
+            }
+        }));
+    }
+    
+    Ok(indexed)
+}
+
+/// In-memory vector index searched by cosine similarity
+pub struct VectorIndex {
+    documents: Vec<IndexedDocument>,
+    embedding_model: ProviderEmbeddingModel,
+    embedding_model_name: String,
+}
+
+impl VectorIndex {
+    /// Create a new vector index from CSV documents
+    pub async fn from_documents(documents: Vec<CsvDocument>, provider: &Provider) -> Result<Self> {
+        let indexed = embed_documents(documents, provider).await?;
+        
+        Ok(Self::from_embedded(indexed, provider))
+    }
+    
+    /// Rebuild an index from previously embedded documents
+    pub fn from_embedded(documents: Vec<IndexedDocument>, provider: &Provider) -> Self {
+        Self {
+            documents,
+            embedding_model: provider.embedding_model(),
+            embedding_model_name: provider.config().embedding_model.clone(),
+        }
+    }
+    
+    /// Number of documents in the index
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+    
+    /// Whether the index holds no documents
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+    
+    /// Name of the embedding model the documents were embedded with
+    pub fn embedding_model_name(&self) -> &str {
+        &self.embedding_model_name
+    }
+    
+    /// The indexed documents with their embeddings
+    pub fn documents(&self) -> &[IndexedDocument] {
+        &self.documents
+    }
+    
+    /// Id number to give the next document added, so existing ids never change
+    pub fn next_document_id(&self) -> usize {
+        self.documents
+            .iter()
+            .filter_map(|doc| doc.document.id.strip_prefix("doc_")?.parse::<usize>().ok())
+            .max()
+            .map_or(0, |max| max + 1)
+    }
+    
+    /// Look up a document by id
+    pub fn get(&self, id: &str) -> Option<&EmbeddableDocument> {
+        self.documents
+            .iter()
+            .map(|doc| &doc.document)
+            .find(|doc| doc.id == id)
+    }
+    
+    /// Source files in the index with their row counts, in first-seen order
+    pub fn sources(&self) -> Vec<(String, usize)> {
+        let mut sources: Vec<(String, usize)> = Vec::new();
+        for doc in &self.documents {
+            match sources.iter_mut().find(|(name, _)| *name == doc.document.source_file) {
+                Some((_, count)) => *count += 1,
+                None => sources.push((doc.document.source_file.clone(), 1)),
+            }
+        }
+        sources
+    }
+    
+    /// Search for similar documents
+    pub async fn search(&self, query: &str, top_k: usize) -> Result<Vec<EmbeddableDocument>> {
+        Ok(self
+            .search_scored(query, top_k, |_| true)
+            .await?
+            .into_iter()
+            .map(|(_, doc)| doc)
+            .collect())
+    }
+    
+    /// Search among the documents accepted by `filter`, returning cosine scores
+    pub async fn search_scored(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: impl Fn(&EmbeddableDocument) -> bool,
+    ) -> Result<Vec<(f64, EmbeddableDocument)>> {
+        let query_embedding = self
+            .embedding_model
+            .embed_text(query)
+            .await
+            .map_err(|source| Error::Embedding {
+                model: self.embedding_model_name.clone(),
+                source,
+            })?;
+        
+        let mut scored: Vec<(f64, &IndexedDocument)> = self
+            .documents
+            .iter()
+            .filter(|doc| filter(&doc.document))
+            .map(|doc| (cosine_similarity(&query_embedding.vec, &doc.embedding), doc))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        
+        Ok(scored
+            .into_iter()
+            .take(top_k)
+            .map(|(score, doc)| (score, doc.document.clone()))
+            .collect())
+    }
+}
+
+/// Cosine similarity of two vectors (0.0 if either is all zeros)
+fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f64>().sqrt();
+    
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    
+    dot / (norm_a * norm_b)
+}
@@ -0,0 +1,342 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::chunking::Chunk;
+use crate::error::{Error, Result};
+use crate::index::{embed_documents, EmbeddableDocument, VectorIndex};
+use crate::loader::{load_csvs_from_directory, load_file, source_name, supported_files, LoadReport};
+use crate::provider::Provider;
+use crate::schema::DatasetSchema;
+use crate::types::IngestResult;
+
+/// Outcome of ingesting a folder
+pub struct Ingestion {
+    /// The built index (None when no documents were found)
+    pub index: Option<VectorIndex>,
+    /// Summary suitable for showing to the user
+    pub result: IngestResult,
+}
+
+/// Load every supported file in a folder and build a vector index from it.
+/// Columns are used according to `schema`; roles detected for new columns are added to it.
+pub async fn ingest_folder(
+    folder_path: &str,
+    provider: &Provider,
+    schema: &mut DatasetSchema,
+) -> Result<Ingestion> {
+    // Load spreadsheet rows, notes, transcripts and PDF pages
+    let mut report = LoadReport::default();
+    let documents = load_csvs_from_directory(folder_path, schema, &mut report)?;
+    
+    let doc_count = documents.len();
+    
+    if doc_count == 0 {
+        return Ok(Ingestion {
+            index: None,
+            result: IngestResult {
+                success: false,
+                documents_ingested: 0,
+                files_processed: 0,
+                message: "No supported files found or all files were empty".to_string(),
+                warnings: report.warnings,
+                dialects: report.dialects,
+            },
+        });
+    }
+    
+    // Count unique files
+    let files: HashSet<_> = documents
+        .iter()
+        .map(|d| d.source_file.clone())
+        .collect();
+    let file_count = files.len();
+    
+    // Build vector index
+    let index = VectorIndex::from_documents(documents, provider).await?;
+    
+    Ok(Ingestion {
+        index: Some(index),
+        result: IngestResult {
+            success: true,
+            documents_ingested: doc_count,
+            files_processed: file_count,
+            message: format!(
+                "Successfully indexed {} documents from {} file(s)",
+                doc_count, file_count
+            ),
+            warnings: report.warnings,
+            dialects: report.dialects,
+        },
+    })
+}
+
+/// Add files and folders to an existing index (or start a new one).
+///
+/// A file that is already indexed has its rows replaced (or dropped if it has
+/// none left); a replaced row keeps its id if it is still at the same row
+/// number. Every other row keeps its id, and new rows get ids after the
+/// highest existing one. `tracked` are the folders and files the index was
+/// built from; adding a different file with the name of an indexed one is an
+/// error rather than a replacement, and re-reading a tracked folder drops the
+/// rows of files that are no longer on disk.
+pub async fn add_sources(
+    index: Option<&VectorIndex>,
+    paths: &[String],
+    tracked: &[String],
+    provider: &Provider,
+    schema: &mut DatasetSchema,
+) -> Result<Ingestion> {
+    if let Some(index) = index {
+        ensure_same_embedding_model(index, provider)?;
+    }
+    
+    // Expand folders into the supported files they contain
+    let mut files = Vec::new();
+    for path in paths {
+        let metadata = fs::metadata(path).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+        if metadata.is_dir() {
+            files.extend(supported_files(path)?);
+        } else {
+            files.push(Path::new(path).to_path_buf());
+        }
+    }
+    
+    check_unique_names(&files, tracked, |name| {
+        index.is_some_and(|index| {
+            index
+                .documents()
+                .iter()
+                .any(|doc| doc.document.is_from_file(name))
+        })
+    })?;
+    
+    let mut doc_id = index.map_or(0, VectorIndex::next_document_id);
+    let mut documents = Vec::new();
+    let mut file_names = Vec::new();
+    let mut report = LoadReport::default();
+    for file in &files {
+        documents.extend(load_file(file, &mut doc_id, schema, &mut report)?);
+        file_names.push(source_name(file));
+    }
+    
+    let doc_count = documents.len();
+    
+    // Rows of re-read files are replaced; re-reading a tracked folder also
+    // drops the rows of files deleted since
+    let rereads_folder = paths
+        .iter()
+        .any(|path| tracked.contains(path) && Path::new(path).is_dir());
+    let on_disk: Option<Vec<String>> = rereads_folder.then(|| {
+        tracked_files(tracked)
+            .iter()
+            .chain(&files)
+            .map(|file| source_name(file))
+            .collect()
+    });
+    let is_replaced = |doc: &EmbeddableDocument| file_names.iter().any(|name| doc.is_from_file(name));
+    let is_deleted = |doc: &EmbeddableDocument| {
+        on_disk
+            .as_ref()
+            .is_some_and(|names| !names.iter().any(|name| doc.is_from_file(name)))
+    };
+    let previous = index.map(VectorIndex::documents).unwrap_or_default();
+    let replaced = previous.iter().filter(|doc| is_replaced(&doc.document)).count();
+    let deleted = previous.iter().filter(|doc| is_deleted(&doc.document)).count();
+    
+    if doc_count == 0 && replaced + deleted == 0 {
+        return Ok(Ingestion {
+            index: None,
+            result: IngestResult {
+                success: false,
+                documents_ingested: 0,
+                files_processed: files.len(),
+                message: "No rows found in the selected files".to_string(),
+                warnings: report.warnings,
+                dialects: report.dialects,
+            },
+        });
+    }
+    
+    // Re-read rows (and chunks) keep their ids so saved citations still resolve
+    let previous_ids: HashMap<(&str, usize, usize), &str> = previous
+        .iter()
+        .map(|doc| &doc.document)
+        .filter(|doc| is_replaced(doc))
+        .map(|doc| (row_key(&doc.source_file, doc.row_number, doc.chunk.as_ref()), doc.id.as_str()))
+        .collect();
+    for doc in &mut documents {
+        let key = row_key(&doc.source_file, doc.row_number, doc.chunk.as_ref());
+        if let Some(id) = previous_ids.get(&key) {
+            doc.id = id.to_string();
+        }
+    }
+    
+    let added = if documents.is_empty() {
+        Vec::new()
+    } else {
+        embed_documents(documents, provider).await?
+    };
+    
+    let mut combined: Vec<_> = previous
+        .iter()
+        .filter(|doc| !is_replaced(&doc.document) && !is_deleted(&doc.document))
+        .cloned()
+        .collect();
+    combined.extend(added);
+    
+    let mut message = format!(
+        "Added {} rows from {} file(s)",
+        doc_count,
+        file_names.len()
+    );
+    if replaced > 0 {
+        message.push_str(&format!(", replacing {} previously indexed rows", replaced));
+    }
+    if deleted > 0 {
+        message.push_str(&format!(", removing {} rows of deleted files", deleted));
+    }
+    
+    Ok(Ingestion {
+        index: Some(VectorIndex::from_embedded(combined, provider)),
+        result: IngestResult {
+            success: true,
+            documents_ingested: doc_count,
+            files_processed: file_names.len(),
+            message,
+            warnings: report.warnings,
+            dialects: report.dialects,
+        },
+    })
+}
+
+/// An index with every row from `file_name` removed, and how many rows that was
+pub fn remove_source(
+    index: &VectorIndex,
+    file_name: &str,
+    provider: &Provider,
+) -> Result<(VectorIndex, usize)> {
+    ensure_same_embedding_model(index, provider)?;
+    
+    let remaining: Vec<_> = index
+        .documents()
+        .iter()
+        .filter(|doc| !doc.document.is_from_file(file_name))
+        .cloned()
+        .collect();
+    
+    let removed = index.len() - remaining.len();
+    if removed == 0 {
+        return Err(Error::SourceNotFound(file_name.to_string()));
+    }
+    
+    Ok((VectorIndex::from_embedded(remaining, provider), removed))
+}
+
+/// Rows are attributed to a file by name only, so refuse two different files
+/// with the same name: among the files being added, or a file being added and
+/// the tracked file an indexed name was read from
+fn check_unique_names(
+    files: &[PathBuf],
+    tracked: &[String],
+    is_indexed: impl Fn(&str) -> bool,
+) -> Result<()> {
+    // Where each tracked file name was read from
+    let mut origins: HashMap<String, PathBuf> = HashMap::new();
+    for file in tracked_files(tracked) {
+        origins.entry(source_name(&file)).or_insert(file);
+    }
+    
+    let mut added: HashMap<String, &PathBuf> = HashMap::new();
+    for file in files {
+        let name = source_name(file);
+        let existing = match added.get(&name) {
+            Some(other) => Some(*other),
+            None if is_indexed(&name) => origins.get(&name),
+            None => None,
+        };
+        if let Some(existing) = existing {
+            if !same_file(existing, file) {
+                return Err(Error::DuplicateSourceName {
+                    name,
+                    existing: existing.display().to_string(),
+                    added: file.display().to_string(),
+                });
+            }
+        }
+        added.insert(name, file);
+    }
+    Ok(())
+}
+
+/// The supported files in the tracked folders and the tracked files that still exist
+fn tracked_files(tracked: &[String]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in tracked {
+        if Path::new(path).is_dir() {
+            files.extend(supported_files(path).unwrap_or_default());
+        } else if Path::new(path).is_file() {
+            files.push(PathBuf::from(path));
+        }
+    }
+    files
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Identifies a row, or one chunk of it, across re-reads
+fn row_key<'a>(
+    source_file: &'a str,
+    row_number: usize,
+    chunk: Option<&Chunk>,
+) -> (&'a str, usize, usize) {
+    (source_file, row_number, chunk.map_or(0, |chunk| chunk.index))
+}
+
+/// New rows must be embedded with the model the index was built with
+fn ensure_same_embedding_model(index: &VectorIndex, provider: &Provider) -> Result<()> {
+    let configured = &provider.config().embedding_model;
+    if index.embedding_model_name() != configured {
+        return Err(Error::EmbeddingModelMismatch {
+            indexed: index.embedding_model_name().to_string(),
+            configured: configured.clone(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_same_name_from_another_folder_is_rejected() {
+        let root = std::env::temp_dir().join(format!("cy-dog-names-{}", std::process::id()));
+        let (first, second) = (root.join("first"), root.join("second"));
+        for dir in [&first, &second] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(dir.join("interviews.csv"), "Pain\nOnboarding\n").unwrap();
+        }
+        let tracked = vec![first.to_string_lossy().to_string()];
+        let indexed = |name: &str| name == "interviews.csv";
+        
+        // Re-reading the tracked file is fine, another file of that name is not
+        assert!(check_unique_names(&[first.join("interviews.csv")], &tracked, indexed).is_ok());
+        let error = check_unique_names(&[second.join("interviews.csv")], &tracked, indexed).unwrap_err();
+        assert!(matches!(error, Error::DuplicateSourceName { ref name, .. } if name == "interviews.csv"));
+        
+        // Two new folders holding files of the same name clash with each other
+        let both = [first.join("interviews.csv"), second.join("interviews.csv")];
+        assert!(check_unique_names(&both, &[], |_| false).is_err());
+        
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
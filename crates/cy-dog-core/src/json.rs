@@ -0,0 +1,223 @@
+//! Survey and CRM exports as JSON arrays or JSON Lines.
+//!
+//! Each object is a row. Nested fields are flattened into dotted columns
+//! (`company.size`), which then get column roles like any spreadsheet column,
+//! so users choose which fields are embedded.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::loader::{table_documents, CsvDocument};
+use crate::notes::read_text;
+use crate::schema::DatasetSchema;
+
+/// Column holding records that are plain values rather than objects
+const VALUE_COLUMN: &str = "value";
+
+/// Parse a `.json` file: an array of records, an object wrapping one
+/// (`{"responses": [...]}`) or a single record
+pub(crate) fn parse_json_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+    schema: &mut DatasetSchema,
+) -> Result<Vec<CsvDocument>> {
+    let text = read_text(file_path)?;
+    let value: Value = serde_json::from_str(&text).map_err(|e| Error::Parse {
+        file: filename.to_string(),
+        row: None,
+        message: format!("Invalid JSON: {}", e),
+    })?;
+
+    Ok(records_to_documents(
+        filename,
+        json_records(value),
+        doc_id,
+        schema,
+    ))
+}
+
+/// Parse a `.jsonl`/`.ndjson` file with one record per line
+pub(crate) fn parse_jsonl_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+    schema: &mut DatasetSchema,
+) -> Result<Vec<CsvDocument>> {
+    let text = read_text(file_path)?;
+    let mut records = Vec::new();
+
+    for (line_idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(line).map_err(|e| Error::Parse {
+            file: filename.to_string(),
+            row: Some(line_idx + 1),
+            message: format!("Invalid JSON: {}", e),
+        })?;
+        records.push(record);
+    }
+
+    Ok(records_to_documents(filename, records, doc_id, schema))
+}
+
+/// The records of a JSON document
+fn json_records(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(records) => records,
+        Value::Object(mut object) => {
+            // Exports often wrap the records: {"count": 2, "responses": [{...}, {...}]}
+            let wrappers: Vec<String> = object
+                .iter()
+                .filter(|(_, value)| {
+                    matches!(value, Value::Array(items) if items.iter().any(Value::is_object))
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            match wrappers.as_slice() {
+                [key] => match object.remove(key) {
+                    Some(Value::Array(records)) => records,
+                    _ => Vec::new(),
+                },
+                _ => vec![Value::Object(object)],
+            }
+        }
+        other => vec![other],
+    }
+}
+
+/// Flatten records into a table whose columns appear in first-seen order
+fn records_to_documents(
+    filename: &str,
+    records: Vec<Value>,
+    doc_id: &mut usize,
+    schema: &mut DatasetSchema,
+) -> Vec<CsvDocument> {
+    let mut headers: Vec<String> = Vec::new();
+    let mut columns: HashMap<String, usize> = HashMap::new();
+    let mut rows = Vec::new();
+
+    for record in &records {
+        let mut fields = Vec::new();
+        flatten_value("", record, &mut fields);
+
+        let mut row = vec![String::new(); headers.len()];
+        for (key, value) in fields {
+            let column = *columns.entry(key.clone()).or_insert_with(|| {
+                headers.push(key);
+                headers.len() - 1
+            });
+            if column >= row.len() {
+                row.resize(column + 1, String::new());
+            }
+            row[column] = value;
+        }
+        rows.push(row);
+    }
+
+    // Earlier rows miss the columns first seen in later ones
+    for row in &mut rows {
+        row.resize(headers.len(), String::new());
+    }
+
+    table_documents(filename, &headers, &rows, schema, doc_id)
+}
+
+/// ("company.size", "50") pairs for every value in a record; lists of plain
+/// values are joined, lists of objects are numbered (`contacts.0.name`)
+fn flatten_value(prefix: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        }
+    };
+
+    match value {
+        Value::Object(object) => {
+            for (name, value) in object {
+                flatten_value(&key(name), value, fields);
+            }
+        }
+        Value::Array(items)
+            if items
+                .iter()
+                .all(|item| !item.is_object() && !item.is_array()) =>
+        {
+            let values: Vec<String> = items.iter().filter_map(scalar_text).collect();
+            fields.push((column_name(prefix), values.join("; ")));
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten_value(&key(&i.to_string()), item, fields);
+            }
+        }
+        scalar => {
+            if let Some(text) = scalar_text(scalar) {
+                fields.push((column_name(prefix), text));
+            }
+        }
+    }
+}
+
+fn column_name(prefix: &str) -> String {
+    if prefix.is_empty() {
+        VALUE_COLUMN.to_string()
+    } else {
+        prefix.to_string()
+    }
+}
+
+/// Text of a string, number or boolean; `None` for null
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::ColumnRole;
+
+    #[test]
+    fn test_nested_records_are_flattened_into_columns() {
+        let records = json_records(serde_json::json!({
+            "exported": "2024-03-02",
+            "responses": [
+                { "id": "r1", "answer": "Scheduling is painful", "company": { "size": 50 }, "tags": ["ops", "smb"] },
+                { "id": "r2", "answer": "We use paper", "contact": null, "interviewer": "DM" },
+            ]
+        }));
+        assert_eq!(records.len(), 2);
+
+        let mut fields = Vec::new();
+        flatten_value("", &records[0], &mut fields);
+        assert!(fields.contains(&("company.size".to_string(), "50".to_string())));
+        assert!(fields.contains(&("tags".to_string(), "ops; smb".to_string())));
+
+        let mut schema = DatasetSchema::default();
+        schema.set_role(None, "tags", ColumnRole::Metadata);
+        let mut doc_id = 0;
+        let docs = records_to_documents("survey.json", records, &mut doc_id, &mut schema);
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(
+            docs[0].content,
+            "From survey.json, Row 1: answer: Scheduling is painful"
+        );
+        assert_eq!(docs[0].metadata["company.size"], "50");
+        assert_eq!(docs[0].metadata["tags"], "ops; smb");
+        assert_eq!(docs[1].row_number, 2);
+        assert_eq!(
+            schema.columns.get("interviewer"),
+            Some(&ColumnRole::Ignored)
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Core of Cy Dog: loading interview spreadsheets and notes, building a
+//! vector index, retrieving relevant rows and generating grounded answers.
+//!
+//! This crate has no Tauri dependency so it can be used from the desktop app,
+//! scripts and tests alike.
+
+pub mod answer_eval;
+pub mod batch;
+pub mod cells;
+pub mod chunking;
+pub mod collection;
+pub mod dialect;
+pub mod error;
+pub mod generation;
+pub mod headers;
+pub mod index;
+pub mod ingest;
+pub mod json;
+pub mod loader;
+pub mod mcp;
+pub mod notes;
+pub mod pdf;
+pub mod provider;
+pub mod retrieval;
+pub mod retrieval_eval;
+pub mod schema;
+pub mod store;
+pub mod transcript;
+pub mod types;
+
+pub use answer_eval::*;
+pub use batch::*;
+pub use cells::*;
+pub use chunking::*;
+pub use collection::*;
+pub use dialect::*;
+pub use error::{Error, Result};
+pub use generation::*;
+pub use index::*;
+pub use ingest::*;
+pub use loader::*;
+pub use mcp::*;
+pub use provider::*;
+pub use retrieval::*;
+pub use retrieval_eval::*;
+pub use schema::*;
+pub use store::*;
+pub use types::*;
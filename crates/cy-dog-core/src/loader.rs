@@ -0,0 +1,526 @@
+use calamine::{Data, Reader, SheetVisible, open_workbook_auto};
+use csv::ReaderBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cells::{render_cell, CellValue, NumberFormat};
+use crate::chunking::{chunk_row, Chunk};
+use crate::dialect::{read_csv_text, CsvDialect};
+use crate::error::{Error, Result};
+use crate::headers::{detect_header, unique_names};
+use crate::json::{parse_json_file, parse_jsonl_file};
+use crate::notes::{parse_docx_file, parse_markdown_file, parse_text_file};
+use crate::pdf::parse_pdf_file;
+use crate::schema::{ColumnRole, DatasetSchema};
+use crate::transcript::parse_transcript_file;
+
+/// A document created from a CSV, Excel or JSON row (or a paragraph of notes, a
+/// transcript turn or a PDF page), ready for embedding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvDocument {
+    /// Unique identifier for the document
+    pub id: String,
+    /// The flattened semantic content for embedding
+    pub content: String,
+    /// Source filename
+    pub source_file: String,
+    /// Original row number (1-indexed)
+    pub row_number: usize,
+    /// Values of the metadata, interviewee and date columns, by header
+    /// (numbers and booleans from workbooks keep their type)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, CellValue>,
+    /// Set when a long row was split into several documents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<Chunk>,
+    /// Where in a non-tabular source the document came from; `None` for spreadsheet rows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+}
+
+/// Position of a document within a source that has no rows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Location {
+    /// A paragraph of notes (numbered by `row_number`) under a heading path like "Pricing > Objections"
+    Paragraph { section: Option<String> },
+    /// A speaker turn (or time window) of a call transcript starting at "HH:MM:SS"
+    Transcript { start: String, speaker: Option<String> },
+    /// A page of a PDF (numbered by `row_number`)
+    Page,
+}
+
+/// What loading noticed besides the documents, for the ingestion report
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    /// Content that was skipped, e.g. excluded sheets or PDF pages without text
+    pub warnings: Vec<String>,
+    /// Encoding and delimiter each CSV file was read with, by file name
+    pub dialects: BTreeMap<String, CsvDialect>,
+}
+
+/// File extensions the loader can read
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "csv", "xlsx", "xls", "xlsm", "xlsb", "ods", "md", "markdown", "txt", "docx", "vtt", "srt", "pdf", "json", "jsonl", "ndjson",
+];
+
+/// Load all supported files (spreadsheets, notes, transcripts and PDFs) from a
+/// directory, using (and extending) the dataset's column roles. Skipped content
+/// and CSV dialects are recorded in `report`.
+pub fn load_csvs_from_directory(
+    folder_path: &str,
+    schema: &mut DatasetSchema,
+    report: &mut LoadReport,
+) -> Result<Vec<CsvDocument>> {
+    let mut all_documents = Vec::new();
+    let mut doc_id = 0;
+    
+    for file_path in supported_files(folder_path)? {
+        all_documents.extend(load_file(&file_path, &mut doc_id, schema, report)?);
+    }
+    
+    Ok(all_documents)
+}
+
+/// The supported files directly inside a directory, sorted by name
+pub fn supported_files(folder_path: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(folder_path);
+    
+    if !path.exists() {
+        return Err(Error::DirectoryNotFound(folder_path.to_string()));
+    }
+    
+    if !path.is_dir() {
+        return Err(Error::NotADirectory(folder_path.to_string()));
+    }
+    
+    // Read all files in the directory
+    let io_error = |source| Error::Io {
+        path: folder_path.to_string(),
+        source,
+    };
+    
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        let file_path = entry.map_err(io_error)?.path();
+        if file_path.is_file() && is_supported_file(&file_path) {
+            files.push(file_path);
+        }
+    }
+    files.sort();
+    
+    Ok(files)
+}
+
+/// Whether the loader can read a file, judging by its name
+pub fn is_supported_file(path: &Path) -> bool {
+    // Office lock files ("~$notes.xlsx") and hidden files are not data
+    let name = source_name(path);
+    if name.starts_with("~$") || name.starts_with('.') {
+        return false;
+    }
+    
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| SUPPORTED_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Load a single supported file, numbering documents from `doc_id`; columns
+/// without a role in `schema` get a detected one, and skipped content and the
+/// CSV dialect are recorded in `report`
+pub fn load_file(
+    file_path: &Path,
+    doc_id: &mut usize,
+    schema: &mut DatasetSchema,
+    report: &mut LoadReport,
+) -> Result<Vec<CsvDocument>> {
+    let filename = source_name(file_path);
+    
+    let extension = file_path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    
+    match extension.as_str() {
+        "csv" => {
+            let (documents, dialect) = parse_csv_file(file_path, &filename, doc_id, schema)?;
+            report.dialects.insert(filename, dialect);
+            Ok(documents)
+        }
+        "xlsx" | "xls" | "xlsm" | "xlsb" | "ods" => {
+            parse_excel_file(file_path, &filename, doc_id, schema, &mut report.warnings)
+        }
+        "md" | "markdown" => parse_markdown_file(file_path, &filename, doc_id),
+        "txt" => parse_text_file(file_path, &filename, doc_id),
+        "docx" => parse_docx_file(file_path, &filename, doc_id),
+        "vtt" | "srt" => parse_transcript_file(file_path, &filename, doc_id),
+        "pdf" => parse_pdf_file(file_path, &filename, doc_id, &mut report.warnings),
+        "json" => parse_json_file(file_path, &filename, doc_id, schema),
+        "jsonl" | "ndjson" => parse_jsonl_file(file_path, &filename, doc_id, schema),
+        _ => Err(Error::Parse {
+            file: filename,
+            row: None,
+            message: format!(
+                "Unsupported file type (expected one of: {})",
+                SUPPORTED_EXTENSIONS.join(", ")
+            ),
+        }),
+    }
+}
+
+/// Name rows from a file are attributed to (its file name)
+pub fn source_name(file_path: &Path) -> String {
+    file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Parse a single Excel or OpenDocument workbook into documents; sheets
+/// excluded in `schema` are skipped and listed in `warnings`, as are cells
+/// holding errors such as `#N/A`
+fn parse_excel_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+    schema: &mut DatasetSchema,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<CsvDocument>> {
+    let mut workbook = open_workbook_auto(file_path).map_err(|e| Error::Parse {
+        file: filename.to_string(),
+        row: None,
+        message: format!("Failed to open Excel workbook: {}", e),
+    })?;
+        
+    let sheets = workbook.sheets_metadata().to_owned();
+    let mut documents = Vec::new();
+    let mut skipped = Vec::new();
+    
+    for sheet in sheets {
+        let sheet_name = sheet.name;
+        if !schema.resolve_sheet(filename, &sheet_name, sheet.visible != SheetVisible::Visible) {
+            skipped.push(sheet_name);
+            continue;
+        }
+        
+        if let Ok(range) = workbook.worksheet_range(&sheet_name) {
+            // The range starts at the first used cell, not necessarily A1
+            let (first_row, first_column) = range
+                .start()
+                .map_or((0, 0), |(row, column)| (row as usize, column as usize));
+            
+            // Render cells by type (ISO dates rather than serial numbers)
+            let cells: Vec<Vec<String>> = range
+                .rows()
+                .map(|row| row.iter().map(|c| render_cell(c, NumberFormat::default()).0).collect())
+                .collect();
+            
+            // Find the header below any title block, combining two-row headers
+            let header = match detect_header(&cells, first_column) {
+                Some(header) => header,
+                None => continue, // Empty sheet
+            };
+            let headers = header.names;
+            
+            // Render the rows below the header in their column's number format,
+            // keeping the typed values for the metadata
+            let formats: Vec<NumberFormat> = headers.iter().map(|h| schema.number_format(h)).collect();
+            let mut rows = Vec::new();
+            let mut typed_rows = Vec::new();
+            let mut errors = BTreeSet::new();
+            let mut error_count = 0;
+            for row in range.rows().skip(header.data_start) {
+                let (values, typed): (Vec<String>, Vec<Option<CellValue>>) = row
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| render_cell(c, formats.get(i).copied().unwrap_or_default()))
+                    .unzip();
+                for cell in row {
+                    if let Data::Error(error) = cell {
+                        errors.insert(error.to_string());
+                        error_count += 1;
+                    }
+                }
+                rows.push(values);
+                typed_rows.push(typed);
+            }
+            
+            let source_file = format!("{} ({})", filename, sheet_name);
+            if error_count > 0 {
+                let errors: Vec<String> = errors.into_iter().collect();
+                warnings.push(format!(
+                    "{}: left out {} cell(s) with errors ({})",
+                    source_file,
+                    error_count,
+                    errors.join(", ")
+                ));
+            }
+            
+            let roles = schema.resolve(filename, &headers, &rows);
+            
+            // Process the rows below the header
+            for (row_idx, (values, typed)) in rows.iter().zip(&typed_rows).enumerate() {
+                // Sheet row number as shown in the spreadsheet (1-indexed)
+                let row_number = first_row + header.data_start + row_idx + 1;
+                
+                // Flatten row (long cells become several chunks)
+                let chunks = flatten_excel_row_to_string(
+                    filename,
+                    &sheet_name,
+                    row_number,
+                    &headers,
+                    &roles,
+                    values
+                );
+                
+                let metadata = typed_row_metadata(&headers, &roles, typed);
+                push_row_documents(&mut documents, chunks, &source_file, row_number, metadata, doc_id);
+            }
+        }
+    }
+    
+    if !skipped.is_empty() {
+        warnings.push(format!("{}: skipped sheet(s) {}", filename, skipped.join(", ")));
+    }
+    
+    Ok(documents)
+}
+
+/// Flatten an Excel row into semantic strings (one unless a long cell is chunked)
+fn flatten_excel_row_to_string(
+    filename: &str,
+    sheet_name: &str,
+    row_number: usize,
+    headers: &[String],
+    roles: &[ColumnRole],
+    values: &[String],
+) -> Vec<(String, Option<Chunk>)> {
+    let parts = embedded_parts(headers, roles, values);
+    
+    if parts.is_empty() {
+        return Vec::new();
+    }
+    
+    let prefix = format!("From {} [Sheet: {}], Row {}: ", filename, sheet_name, row_number);
+    chunk_row(&prefix, &parts)
+}
+
+/// Parse a single CSV file into documents, detecting its encoding and delimiter
+fn parse_csv_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+    schema: &mut DatasetSchema,
+) -> Result<(Vec<CsvDocument>, CsvDialect)> {
+    let bytes = fs::read(file_path).map_err(|source| Error::Io {
+        path: file_path.display().to_string(),
+        source,
+    })?;
+    
+    // Transcode to UTF-8 (Excel exports are often Windows-1252 or UTF-16)
+    let (text, dialect) = read_csv_text(&bytes);
+    
+    let mut reader = ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .delimiter(dialect.delimiter as u8)
+        .quote(dialect.quote as u8)
+        .from_reader(text.as_bytes());
+    
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| Error::Parse {
+            file: filename.to_string(),
+            row: None,
+            message: format!("Failed to read CSV headers: {}", e),
+        })?
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    
+    if headers.is_empty() {
+        return Ok((Vec::new(), dialect));
+    }
+    
+    // Name blank headers and tell repeated ones apart
+    let headers = unique_names(headers, 0);
+    
+    // Read every row; column roles are detected from the values
+    let mut rows = Vec::new();
+    for (row_idx, result) in reader.records().enumerate() {
+        let record = result.map_err(|e| Error::Parse {
+            file: filename.to_string(),
+            row: Some(row_idx + 1),
+            message: e.to_string(),
+        })?;
+        let values: Vec<String> = record.iter().map(|value| value.to_string()).collect();
+        rows.push(values);
+    }
+    
+    Ok((table_documents(filename, &headers, &rows, schema, doc_id), dialect))
+}
+
+/// Documents for the rows of a table (a CSV file or JSON records), numbered from 1
+pub(crate) fn table_documents(
+    filename: &str,
+    headers: &[String],
+    rows: &[Vec<String>],
+    schema: &mut DatasetSchema,
+    doc_id: &mut usize,
+) -> Vec<CsvDocument> {
+    let roles = schema.resolve(filename, headers, rows);
+    
+    let mut documents = Vec::new();
+    
+    for (row_idx, values) in rows.iter().enumerate() {
+        let row_number = row_idx + 1; // 1-indexed for human readability
+        
+        // Flatten the row into semantic strings
+        let chunks = flatten_row_to_string(filename, row_number, headers, &roles, values);
+        
+        // Skip empty rows
+        if chunks.is_empty() || !has_meaningful_content(values) {
+            continue;
+        }
+        
+        let metadata = row_metadata(headers, &roles, values);
+        push_row_documents(&mut documents, chunks, filename, row_number, metadata, doc_id);
+    }
+    
+    documents
+}
+
+/// Flatten a CSV row into semantic strings
+/// Format: "From [Filename], Row [Number]: [Column1 Header]: [Value1], [Column2 Header]: [Value2]..."
+/// Only columns whose role is embedded are included; rows too long to embed
+/// are split into chunks that each start with the same citation.
+fn flatten_row_to_string(
+    filename: &str,
+    row_number: usize,
+    headers: &[String],
+    roles: &[ColumnRole],
+    values: &[String],
+) -> Vec<(String, Option<Chunk>)> {
+    let parts = embedded_parts(headers, roles, values);
+    
+    if parts.is_empty() {
+        return Vec::new();
+    }
+    
+    let prefix = format!("From {}, Row {}: ", filename, row_number);
+    chunk_row(&prefix, &parts)
+}
+
+/// (header, value) for every non-empty value of an embedded column
+fn embedded_parts(
+    headers: &[String],
+    roles: &[ColumnRole],
+    values: &[String],
+) -> Vec<(String, String)> {
+    let mut parts = Vec::new();
+    
+    for ((header, role), value) in headers.iter().zip(roles).zip(values) {
+        let value = value.trim();
+        if role.is_embedded() && !value.is_empty() {
+            parts.push((header.clone(), value.to_string()));
+        }
+    }
+    
+    parts
+}
+
+/// Add one document per chunk of a row, all citing the same file and row
+fn push_row_documents(
+    documents: &mut Vec<CsvDocument>,
+    chunks: Vec<(String, Option<Chunk>)>,
+    source_file: &str,
+    row_number: usize,
+    metadata: BTreeMap<String, CellValue>,
+    doc_id: &mut usize,
+) {
+    for (content, chunk) in chunks {
+        documents.push(CsvDocument {
+            id: format!("doc_{}", *doc_id),
+            content,
+            source_file: source_file.to_string(),
+            row_number,
+            metadata: metadata.clone(),
+            chunk,
+            location: None,
+        });
+        
+        *doc_id += 1;
+    }
+}
+
+/// Non-empty values of the metadata columns, by header
+fn row_metadata(
+    headers: &[String],
+    roles: &[ColumnRole],
+    values: &[String],
+) -> BTreeMap<String, CellValue> {
+    let values: Vec<Option<CellValue>> = values
+        .iter()
+        .map(|value| Some(CellValue::from(value.trim())))
+        .collect();
+    typed_row_metadata(headers, roles, &values)
+}
+
+/// Non-empty typed values of the metadata columns, by header
+fn typed_row_metadata(
+    headers: &[String],
+    roles: &[ColumnRole],
+    values: &[Option<CellValue>],
+) -> BTreeMap<String, CellValue> {
+    headers
+        .iter()
+        .zip(roles)
+        .zip(values)
+        .filter(|((_, role), _)| role.is_metadata())
+        .filter_map(|((header, _), value)| {
+            value
+                .clone()
+                .filter(|value| !value.is_empty())
+                .map(|value| (header.clone(), value))
+        })
+        .collect()
+}
+
+/// Check if a row has any meaningful (non-empty) content
+fn has_meaningful_content(values: &[String]) -> bool {
+    values.iter().any(|value| !value.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_flatten_row() {
+        let headers = vec!["Name".to_string(), "Age".to_string(), "City".to_string()];
+        let values = vec!["John Doe".to_string(), "30".to_string(), "New York".to_string()];
+        let roles = vec![ColumnRole::Interviewee, ColumnRole::Text, ColumnRole::Text];
+        
+        let result = &flatten_row_to_string("test.csv", 1, &headers, &roles, &values)[0].0;
+        
+        assert!(result.contains("From test.csv, Row 1:"));
+        assert!(result.contains("Name: John Doe"));
+        assert!(result.contains("Age: 30"));
+        assert!(result.contains("City: New York"));
+    }
+    
+    #[test]
+    fn test_roles_split_content_and_metadata() {
+        let headers = vec!["ID".to_string(), "Interviewer".to_string(), "Pain".to_string()];
+        let values = vec!["r-17".to_string(), "DM".to_string(), "Onboarding".to_string()];
+        let roles = vec![ColumnRole::Metadata, ColumnRole::Ignored, ColumnRole::Text];
+        
+        let result = flatten_row_to_string("test.csv", 1, &headers, &roles, &values);
+        assert_eq!(result, vec![("From test.csv, Row 1: Pain: Onboarding".to_string(), None)]);
+        
+        let metadata = row_metadata(&headers, &roles, &values);
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata["ID"], "r-17");
+    }
+}
@@ -0,0 +1,312 @@
+//! A Model Context Protocol server exposing the interview index as tools.
+//!
+//! Messages are JSON-RPC 2.0 objects, one per line (the MCP stdio transport).
+//! The transport itself lives in the CLI; this module only maps requests to
+//! responses so it can be driven from tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use crate::cells::CellValue;
+use crate::index::{EmbeddableDocument, VectorIndex};
+
+/// MCP protocol revision we implement
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Rows returned by `search_interviews` when the caller gives no `top_k`
+const DEFAULT_TOP_K: usize = 5;
+
+/// Upper bound on `top_k` so a single call cannot dump the whole corpus
+const MAX_TOP_K: usize = 50;
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serves `search_interviews`, `get_row` and `list_sources` over an index
+pub struct McpServer {
+    index: VectorIndex,
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Optional restrictions for `search_interviews`
+#[derive(Debug, Default, Deserialize)]
+struct SearchFilters {
+    /// Only rows whose source file contains this text (case-insensitive)
+    #[serde(default)]
+    source_file: Option<String>,
+    /// Only rows whose metadata columns have these values (numbers compare
+    /// as numbers, text case-insensitively)
+    #[serde(default)]
+    metadata: BTreeMap<String, CellValue>,
+}
+
+impl SearchFilters {
+    fn matches(&self, doc: &EmbeddableDocument) -> bool {
+        let source_matches = self
+            .source_file
+            .as_ref()
+            .is_none_or(|f| doc.source_file.to_lowercase().contains(&f.to_lowercase()));
+        source_matches
+            && self.metadata.iter().all(|(column, expected)| {
+                doc.metadata
+                    .get(column)
+                    .is_some_and(|value| value.matches(expected))
+            })
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchArgs {
+    query: String,
+    #[serde(default)]
+    filters: SearchFilters,
+    #[serde(default)]
+    top_k: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct GetRowArgs {
+    id: String,
+}
+
+/// A search hit as returned to the agent
+#[derive(Serialize)]
+struct SearchHit<'a> {
+    id: &'a str,
+    citation: String,
+    score: f64,
+    content: &'a str,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: &'a BTreeMap<String, CellValue>,
+}
+
+impl McpServer {
+    pub fn new(index: VectorIndex) -> Self {
+        Self { index }
+    }
+
+    /// Handle one JSON-RPC message; notifications produce no response
+    pub async fn handle(&self, message: &str) -> Option<Value> {
+        let request: RpcRequest = match serde_json::from_str(message) {
+            Ok(request) => request,
+            Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+        };
+
+        // Requests without an id are notifications (e.g. notifications/initialized)
+        let id = request.id?;
+
+        let result = match request.method.as_str() {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "cy-dog", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&request.params).await,
+            other => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", other))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    async fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+        let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+
+        let outcome = match name {
+            "search_interviews" => self.search_interviews(parse_args(arguments)?).await,
+            "get_row" => self.get_row(parse_args(arguments)?),
+            "list_sources" => Ok(self.list_sources()),
+            other => return Err((INVALID_PARAMS, format!("Unknown tool: {}", other))),
+        };
+
+        // Tool failures are reported to the agent as content, not protocol errors
+        Ok(match outcome {
+            Ok(text) => json!({ "content": [{ "type": "text", "text": text }] }),
+            Err(message) => json!({
+                "content": [{ "type": "text", "text": message }],
+                "isError": true,
+            }),
+        })
+    }
+
+    async fn search_interviews(&self, args: SearchArgs) -> Result<String, String> {
+        let top_k = args.top_k.unwrap_or(DEFAULT_TOP_K).clamp(1, MAX_TOP_K);
+
+        let results = self
+            .index
+            .search_scored(&args.query, top_k, |doc| args.filters.matches(doc))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let hits: Vec<SearchHit> = results
+            .iter()
+            .map(|(score, doc)| SearchHit {
+                id: &doc.id,
+                citation: doc.citation(),
+                score: *score,
+                content: &doc.content,
+                metadata: &doc.metadata,
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&hits).map_err(|e| e.to_string())
+    }
+
+    fn get_row(&self, args: GetRowArgs) -> Result<String, String> {
+        let doc = self
+            .index
+            .get(&args.id)
+            .ok_or_else(|| format!("No row with id {}", args.id))?;
+
+        serde_json::to_string_pretty(&json!({
+            "id": doc.id,
+            "citation": doc.citation(),
+            "source_file": doc.source_file,
+            "row_number": doc.row_number,
+            "content": doc.content,
+            "metadata": doc.metadata,
+        }))
+        .map_err(|e| e.to_string())
+    }
+
+    fn list_sources(&self) -> String {
+        let sources: Vec<Value> = self
+            .index
+            .sources()
+            .into_iter()
+            .map(|(source_file, rows)| json!({ "source_file": source_file, "rows": rows }))
+            .collect();
+
+        Value::Array(sources).to_string()
+    }
+}
+
+fn parse_args<T: for<'de> Deserialize<'de>>(arguments: Value) -> Result<T, (i64, String)> {
+    serde_json::from_value(arguments).map_err(|e| (INVALID_PARAMS, e.to_string()))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "search_interviews",
+            "description": "Semantic search over the customer discovery interview rows. Returns the most relevant rows with their citation (file and row number), similarity score and content.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "What to look for" },
+                    "filters": {
+                        "type": "object",
+                        "properties": {
+                            "source_file": {
+                                "type": "string",
+                                "description": "Only search rows from files whose name contains this text"
+                            },
+                            "metadata": {
+                                "type": "object",
+                                "additionalProperties": { "type": ["string", "number", "boolean"] },
+                                "description": "Only search rows whose metadata columns (e.g. an interviewee or date column) have exactly these values"
+                            }
+                        }
+                    },
+                    "top_k": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_TOP_K,
+                        "description": "Number of rows to return (default 5)"
+                    }
+                },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "get_row",
+            "description": "Fetch a single interview row by the id returned from search_interviews.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" }
+                },
+                "required": ["id"]
+            }
+        },
+        {
+            "name": "list_sources",
+            "description": "List the indexed source files and how many rows each contributed.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::IndexedDocument;
+    use crate::provider::{Provider, ProviderConfig};
+
+    fn server() -> McpServer {
+        let provider = Provider::new(ProviderConfig::default());
+        let documents = vec![IndexedDocument {
+            document: EmbeddableDocument {
+                id: "doc_0".to_string(),
+                content: "From a.csv, Row 1: Pain: onboarding".to_string(),
+                source_file: "a.csv".to_string(),
+                row_number: 1,
+                metadata: Default::default(),
+                chunk: None,
+                location: None,
+            },
+            embedding: vec![1.0, 0.0],
+        }];
+        McpServer::new(VectorIndex::from_embedded(documents, &provider))
+    }
+
+    #[tokio::test]
+    async fn test_get_row_and_unknown_method() {
+        let server = server();
+
+        let response = server
+            .handle(r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"get_row","arguments":{"id":"doc_0"}}}"#)
+            .await
+            .unwrap();
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("a.csv, Row 1"));
+
+        let response = server
+            .handle(r#"{"jsonrpc":"2.0","id":2,"method":"resources/list"}"#)
+            .await
+            .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        assert!(server
+            .handle(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+            .await
+            .is_none());
+    }
+}
@@ -0,0 +1,365 @@
+//! Interview notes written up as Markdown, plain text or Word documents.
+//!
+//! Notes are split into paragraphs. Each paragraph becomes a document cited
+//! by file, heading path and paragraph number, e.g.
+//! "acme.md, Pricing > Objections, Paragraph 3".
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use crate::chunking::chunk_text;
+use crate::dialect::decode_text;
+use crate::error::{Error, Result};
+use crate::loader::{CsvDocument, Location};
+
+/// A paragraph of notes and the headings it sits under
+#[derive(Debug, PartialEq)]
+struct Paragraph {
+    headings: Vec<String>,
+    text: String,
+}
+
+/// Headings enclosing the current position, with their levels
+#[derive(Default)]
+struct Outline {
+    headings: Vec<(usize, String)>,
+}
+
+impl Outline {
+    fn enter(&mut self, level: usize, title: &str) {
+        while self.headings.last().is_some_and(|(l, _)| *l >= level) {
+            self.headings.pop();
+        }
+        self.headings.push((level, title.trim().to_string()));
+    }
+
+    fn paragraph(&self, text: &str) -> Paragraph {
+        Paragraph {
+            headings: self
+                .headings
+                .iter()
+                .map(|(_, title)| title.clone())
+                .collect(),
+            text: text.trim().to_string(),
+        }
+    }
+}
+
+/// Parse a Markdown file, using `#` headings as sections
+pub(crate) fn parse_markdown_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+) -> Result<Vec<CsvDocument>> {
+    let text = read_text(file_path)?;
+    Ok(paragraph_documents(
+        filename,
+        markdown_paragraphs(&text),
+        doc_id,
+    ))
+}
+
+/// Parse a plain-text file; paragraphs are separated by blank lines
+pub(crate) fn parse_text_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+) -> Result<Vec<CsvDocument>> {
+    let text = read_text(file_path)?;
+    let paragraphs = blocks(&text)
+        .into_iter()
+        .map(|block| Outline::default().paragraph(&block))
+        .collect();
+    Ok(paragraph_documents(filename, paragraphs, doc_id))
+}
+
+/// Parse a Word document, using its Title and Heading styles as sections
+pub(crate) fn parse_docx_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+) -> Result<Vec<CsvDocument>> {
+    let parse_error = |message: String| Error::Parse {
+        file: filename.to_string(),
+        row: None,
+        message,
+    };
+
+    let file = fs::File::open(file_path).map_err(|source| Error::Io {
+        path: file_path.display().to_string(),
+        source,
+    })?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| parse_error(format!("Not a Word document: {}", e)))?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| parse_error(format!("Not a Word document: {}", e)))?
+        .read_to_string(&mut xml)
+        .map_err(|e| parse_error(format!("Failed to read document body: {}", e)))?;
+
+    let paragraphs = docx_paragraphs(&xml)
+        .map_err(|e| parse_error(format!("Failed to read document body: {}", e)))?;
+    Ok(paragraph_documents(filename, paragraphs, doc_id))
+}
+
+/// A text file's contents, decoded from its detected encoding so notes saved
+/// as Windows-1252 or UTF-16 load too
+pub(crate) fn read_text(file_path: &Path) -> Result<String> {
+    let bytes = fs::read(file_path).map_err(|source| Error::Io {
+        path: file_path.display().to_string(),
+        source,
+    })?;
+    Ok(decode_text(&bytes).0)
+}
+
+/// Number paragraphs from 1 and turn each (or each chunk of a long one) into a document
+fn paragraph_documents(
+    filename: &str,
+    paragraphs: Vec<Paragraph>,
+    doc_id: &mut usize,
+) -> Vec<CsvDocument> {
+    let mut documents = Vec::new();
+
+    for (i, paragraph) in paragraphs.into_iter().enumerate() {
+        let number = i + 1;
+        let section = (!paragraph.headings.is_empty()).then(|| paragraph.headings.join(" > "));
+        let prefix = match &section {
+            Some(section) => format!(
+                "From {} [Section: {}], Paragraph {}: ",
+                filename, section, number
+            ),
+            None => format!("From {}, Paragraph {}: ", filename, number),
+        };
+
+        for (content, chunk) in chunk_text(&prefix, &paragraph.text) {
+            documents.push(CsvDocument {
+                id: format!("doc_{}", *doc_id),
+                content,
+                source_file: filename.to_string(),
+                row_number: number,
+                metadata: Default::default(),
+                chunk,
+                location: Some(Location::Paragraph {
+                    section: section.clone(),
+                }),
+            });
+            *doc_id += 1;
+        }
+    }
+
+    documents
+}
+
+/// Non-empty blocks of text separated by blank lines
+fn blocks(text: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            flush(&mut current, &mut blocks);
+        } else {
+            current.push(line);
+        }
+    }
+    flush(&mut current, &mut blocks);
+    blocks
+}
+
+fn flush(lines: &mut Vec<&str>, blocks: &mut Vec<String>) {
+    if !lines.is_empty() {
+        blocks.push(lines.join("\n"));
+        lines.clear();
+    }
+}
+
+/// Paragraphs of a Markdown document under their ATX (`#`) headings
+fn markdown_paragraphs(text: &str) -> Vec<Paragraph> {
+    let mut outline = Outline::default();
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut in_code = false;
+    let mut lines = text.lines().peekable();
+
+    // YAML front matter is metadata, not notes
+    if lines.peek().is_some_and(|line| line.trim() == "---") {
+        lines.next();
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+        }
+    }
+
+    let mut end_paragraph = |current: &mut Vec<&str>, outline: &Outline| {
+        if !current.is_empty() {
+            paragraphs.push(outline.paragraph(&current.join("\n")));
+            current.clear();
+        }
+    };
+
+    for line in lines {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            current.push(line);
+            continue;
+        }
+        if in_code {
+            current.push(line);
+            continue;
+        }
+
+        if let Some((level, title)) = atx_heading(trimmed) {
+            end_paragraph(&mut current, &outline);
+            outline.enter(level, title);
+        } else if trimmed.is_empty() {
+            end_paragraph(&mut current, &outline);
+        } else {
+            current.push(line);
+        }
+    }
+    end_paragraph(&mut current, &outline);
+
+    paragraphs
+}
+
+/// "## Pricing" -> (2, "Pricing")
+fn atx_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')) {
+        Some((level, rest.trim().trim_end_matches('#').trim()))
+    } else {
+        None
+    }
+}
+
+/// Paragraphs of a Word document body (`word/document.xml`)
+fn docx_paragraphs(xml: &str) -> std::result::Result<Vec<Paragraph>, quick_xml::Error> {
+    let mut reader = Reader::from_str(xml);
+    let mut outline = Outline::default();
+    let mut paragraphs = Vec::new();
+
+    let mut text = String::new();
+    let mut heading_level = None;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if e.name().as_ref() == b"w:p" => {
+                text.clear();
+                heading_level = None;
+            }
+            Event::Start(e) if e.name().as_ref() == b"w:t" => in_text = true,
+            Event::End(e) if e.name().as_ref() == b"w:t" => in_text = false,
+            Event::Text(e) if in_text => text.push_str(&e.unescape()?),
+            Event::Empty(e) => match e.name().as_ref() {
+                b"w:pStyle" => {
+                    if let Some(style) = e.try_get_attribute("w:val")? {
+                        heading_level =
+                            heading_style_level(&style.decode_and_unescape_value(&reader)?);
+                    }
+                }
+                b"w:tab" => text.push('\t'),
+                b"w:br" | b"w:cr" => text.push('\n'),
+                _ => {}
+            },
+            Event::End(e) if e.name().as_ref() == b"w:p" => {
+                if text.trim().is_empty() {
+                    continue;
+                }
+                match heading_level {
+                    Some(level) => outline.enter(level, &text),
+                    None => paragraphs.push(outline.paragraph(&text)),
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(paragraphs)
+}
+
+/// Outline level of a paragraph style id ("Title", "Heading2", ...)
+fn heading_style_level(style: &str) -> Option<usize> {
+    let style = style.to_lowercase();
+    if style == "title" {
+        return Some(1);
+    }
+    style
+        .strip_prefix("heading")
+        .and_then(|level| level.trim().parse::<usize>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_paragraphs_follow_heading_path() {
+        let notes = "---\ntitle: Acme\n---\n# Acme call\n\nIntro paragraph.\n\n## Pricing\n### Objections\nToo expensive\nfor small teams.\n\n## Next steps\nSend a quote.\n";
+
+        let paragraphs = markdown_paragraphs(notes);
+        let sections: Vec<(String, &str)> = paragraphs
+            .iter()
+            .map(|p| (p.headings.join(" > "), p.text.as_str()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Acme call".to_string(), "Intro paragraph."),
+                (
+                    "Acme call > Pricing > Objections".to_string(),
+                    "Too expensive\nfor small teams."
+                ),
+                ("Acme call > Next steps".to_string(), "Send a quote."),
+            ]
+        );
+
+        let mut doc_id = 0;
+        let docs = paragraph_documents("acme.md", paragraphs, &mut doc_id);
+        assert_eq!(docs[1].row_number, 2);
+        assert!(docs[1].content.starts_with(
+            "From acme.md [Section: Acme call > Pricing > Objections], Paragraph 2: "
+        ));
+    }
+
+    #[test]
+    fn test_windows_1252_text_notes_are_decoded() {
+        let dir = std::env::temp_dir().join(format!("cy-dog-notes-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("README.txt");
+        fs::write(&path, b"Caf\xe9 owners want na\xefve pricing.\r\n").unwrap();
+
+        let docs = parse_text_file(&path, "README.txt", &mut 0).unwrap();
+        assert_eq!(
+            docs[0].content,
+            "From README.txt, Paragraph 1: Café owners want naïve pricing."
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_docx_headings_and_paragraphs() {
+        let xml = r#"<w:document><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Onboarding</w:t></w:r></w:p>
+            <w:p><w:r><w:t xml:space="preserve">Setup took </w:t></w:r><w:r><w:t>two days &amp; a call.</w:t></w:r></w:p>
+            <w:p><w:r><w:t></w:t></w:r></w:p>
+        </w:body></w:document>"#;
+
+        assert_eq!(
+            docx_paragraphs(xml).unwrap(),
+            vec![Paragraph {
+                headings: vec!["Onboarding".to_string()],
+                text: "Setup took two days & a call.".to_string(),
+            }]
+        );
+    }
+}
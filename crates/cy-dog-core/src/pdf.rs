@@ -0,0 +1,112 @@
+//! Interview summaries and survey reports delivered as PDF.
+//!
+//! Text is extracted page by page; each page becomes a document cited as
+//! "report.pdf, Page 4". Pages without a text layer (scanned images) are
+//! skipped and reported as a warning.
+
+use std::fs;
+use std::panic;
+use std::path::Path;
+
+use crate::chunking::chunk_text;
+use crate::error::{Error, Result};
+use crate::loader::{CsvDocument, Location};
+
+/// Parse a PDF into one document per page (more for long pages)
+pub(crate) fn parse_pdf_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<CsvDocument>> {
+    let parse_error = |message: String| Error::Parse {
+        file: filename.to_string(),
+        row: None,
+        message,
+    };
+
+    let bytes = fs::read(file_path).map_err(|source| Error::Io {
+        path: file_path.display().to_string(),
+        source,
+    })?;
+
+    // The extractor panics on some malformed files; treat that like any other parse failure
+    let pages = panic::catch_unwind(|| pdf_extract::extract_text_from_mem_by_pages(&bytes))
+        .map_err(|_| parse_error("The PDF could not be read".to_string()))?
+        .map_err(|e| parse_error(format!("Failed to extract text: {}", e)))?;
+
+    Ok(page_documents(filename, &pages, doc_id, warnings))
+}
+
+/// Number pages from 1 and turn each page with text into documents
+fn page_documents(
+    filename: &str,
+    pages: &[String],
+    doc_id: &mut usize,
+    warnings: &mut Vec<String>,
+) -> Vec<CsvDocument> {
+    let mut documents = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (i, page) in pages.iter().enumerate() {
+        let number = i + 1;
+        let text = page.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            skipped.push(number.to_string());
+            continue;
+        }
+
+        let prefix = format!("From {}, Page {}: ", filename, number);
+        for (content, chunk) in chunk_text(&prefix, &text) {
+            documents.push(CsvDocument {
+                id: format!("doc_{}", *doc_id),
+                content,
+                source_file: filename.to_string(),
+                row_number: number,
+                metadata: Default::default(),
+                chunk,
+                location: Some(Location::Page),
+            });
+            *doc_id += 1;
+        }
+    }
+
+    if !skipped.is_empty() {
+        warnings.push(format!(
+            "{}: skipped {} page(s) without extractable text, probably scanned images (pages {})",
+            filename,
+            skipped.len(),
+            skipped.join(", ")
+        ));
+    }
+
+    documents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_only_pages_are_skipped_with_a_warning() {
+        let pages = vec![
+            "Survey results\n\n  42% plan shifts   on paper.".to_string(),
+            "\n \n".to_string(),
+            "Appendix".to_string(),
+        ];
+
+        let mut doc_id = 0;
+        let mut warnings = Vec::new();
+        let docs = page_documents("report.pdf", &pages, &mut doc_id, &mut warnings);
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(
+            docs[0].content,
+            "From report.pdf, Page 1: Survey results 42% plan shifts on paper."
+        );
+        assert_eq!(docs[1].row_number, 3);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("report.pdf: skipped 1 page(s)"));
+        assert!(warnings[0].ends_with("(pages 2)"));
+    }
+}
@@ -0,0 +1,695 @@
+use futures::stream::{self, BoxStream, StreamExt};
+use rig::{
+    completion::Prompt,
+    embeddings::{Embedding, EmbeddingError, EmbeddingModel},
+    providers::{ollama, openai},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::types::{
+    ChatModel, OllamaPsResponse, OllamaShowResponse, OllamaTagsResponse, ProviderStatus,
+};
+
+/// Default base URL of the local Ollama API
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// The default embedding model (used with Ollama)
+pub const EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+/// Chat model used until the user picks one
+pub const DEFAULT_CHAT_MODEL: &str = "llama3";
+
+/// File in the data directory holding the settings chosen in the desktop app
+const SETTINGS_FILE_NAME: &str = "provider.json";
+
+/// Known embedding-only models that should be filtered from chat selection
+const EMBEDDING_MODELS: &[&str] = &[
+    "nomic-embed-text",
+    "all-minilm",
+    "mxbai-embed-large",
+    "bge-m3",
+    "bge-large",
+    "snowflake-arctic-embed",
+    "paraphrase-multilingual",
+    "granite-embedding",
+    "embeddinggemma",
+    "qwen3-embedding",
+];
+
+/// Which kind of LLM backend to talk to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    /// A local Ollama server
+    Ollama,
+    /// Any server speaking the OpenAI API (llama.cpp server, LM Studio, vLLM, ...)
+    OpenAiCompatible,
+}
+
+impl ProviderKind {
+    /// How messages name the backend
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ollama => "Ollama",
+            Self::OpenAiCompatible => "the OpenAI-compatible server",
+        }
+    }
+}
+
+/// User-selectable backend settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    pub kind: ProviderKind,
+    /// Server base URL (e.g. "http://localhost:11434" or "http://localhost:8080/v1")
+    pub base_url: String,
+    /// Optional API key sent as a bearer token (OpenAI-compatible only)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model used to embed documents and queries
+    pub embedding_model: String,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            kind: ProviderKind::Ollama,
+            base_url: DEFAULT_OLLAMA_URL.to_string(),
+            api_key: None,
+            embedding_model: EMBEDDING_MODEL.to_string(),
+        }
+    }
+}
+
+impl ProviderConfig {
+    /// Settings saved in `dir`, or the defaults if none were saved yet
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(SETTINGS_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let io_error = |source: std::io::Error| Error::Io {
+            path: path.display().to_string(),
+            source,
+        };
+        let bytes = fs::read(&path).map_err(io_error)?;
+        serde_json::from_slice(&bytes).map_err(|e| io_error(e.into()))
+    }
+
+    /// Save the settings to `dir`. The file may hold an API key, so it is
+    /// only readable by the current user.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(SETTINGS_FILE_NAME);
+        let tmp_path = path.with_extension("json.tmp");
+        let io_error = |path: &Path, source: std::io::Error| Error::Io {
+            path: path.display().to_string(),
+            source,
+        };
+
+        fs::create_dir_all(dir).map_err(|source| io_error(dir, source))?;
+        let json = serde_json::to_vec_pretty(self).map_err(|e| io_error(&path, e.into()))?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        // Write-then-rename like the collection manifest
+        options
+            .open(&tmp_path)
+            .and_then(|mut file| file.write_all(&json))
+            .map_err(|source| io_error(&tmp_path, source))?;
+        fs::rename(&tmp_path, &path).map_err(|source| io_error(&path, source))
+    }
+}
+
+/// A configured LLM backend covering chat, embeddings and model listing
+#[derive(Clone)]
+pub struct Provider {
+    config: ProviderConfig,
+    client: ProviderClient,
+}
+
+#[derive(Clone)]
+enum ProviderClient {
+    Ollama(ollama::Client),
+    OpenAi(openai::Client),
+}
+
+impl Provider {
+    pub fn new(config: ProviderConfig) -> Self {
+        let base_url = config.base_url.trim_end_matches('/');
+        let client = match config.kind {
+            ProviderKind::Ollama => ProviderClient::Ollama(ollama::Client::from_url(base_url)),
+            ProviderKind::OpenAiCompatible => ProviderClient::OpenAi(openai::Client::from_url(
+                config.api_key.as_deref().unwrap_or_default(),
+                base_url,
+            )),
+        };
+
+        Self { config, client }
+    }
+
+    pub fn config(&self) -> &ProviderConfig {
+        &self.config
+    }
+
+    /// The embedding model configured for this provider
+    pub fn embedding_model(&self) -> ProviderEmbeddingModel {
+        let model = self.config.embedding_model.as_str();
+        match &self.client {
+            ProviderClient::Ollama(client) => {
+                ProviderEmbeddingModel::Ollama(client.embedding_model(model))
+            }
+            ProviderClient::OpenAi(client) => {
+                ProviderEmbeddingModel::OpenAi(client.embedding_model(model))
+            }
+        }
+    }
+
+    /// Send a single prompt to a chat model with the given system preamble
+    pub async fn prompt(&self, model_name: &str, preamble: &str, prompt: &str) -> Result<String> {
+        let response = match &self.client {
+            ProviderClient::Ollama(client) => {
+                client
+                    .agent(model_name)
+                    .preamble(preamble)
+                    .build()
+                    .prompt(prompt)
+                    .await
+            }
+            ProviderClient::OpenAi(client) => {
+                client
+                    .agent(model_name)
+                    .preamble(preamble)
+                    .build()
+                    .prompt(prompt)
+                    .await
+            }
+        };
+
+        response.map_err(|source| Error::Completion {
+            model: model_name.to_string(),
+            source,
+        })
+    }
+
+    /// Send a single prompt and stream the reply as text fragments
+    pub async fn stream_prompt(
+        &self,
+        model_name: &str,
+        preamble: &str,
+        prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>> {
+        let messages = serde_json::json!([
+            { "role": "system", "content": preamble },
+            { "role": "user", "content": prompt },
+        ]);
+        let body = serde_json::json!({
+            "model": model_name,
+            "messages": messages,
+            "stream": true,
+        });
+
+        let http = reqwest::Client::new();
+        let request = match self.config.kind {
+            ProviderKind::Ollama => http.post(self.url("/api/chat")),
+            ProviderKind::OpenAiCompatible => {
+                self.authorized(http.post(self.url("/chat/completions")))
+            }
+        };
+
+        let url = self.config.base_url.clone();
+        let response = request
+            .json(&body)
+            .send()
+            .await
+            .map_err(|source| Error::Connection {
+                url: url.clone(),
+                source,
+            })?;
+
+        if !response.status().is_success() {
+            return Err(Error::InvalidResponse {
+                url,
+                message: format!("error status {}", response.status()),
+            });
+        }
+
+        let kind = self.config.kind;
+        let tokens = lines(response, url)
+            .filter_map(move |line| async move {
+                match line {
+                    Ok(line) => parse_stream_line(kind, &line).transpose(),
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .boxed();
+
+        Ok(tokens)
+    }
+
+    /// Names of every model the server offers, including embedding models
+    pub async fn list_model_names(&self) -> Result<Vec<String>> {
+        let http = reqwest::Client::new();
+        match self.config.kind {
+            ProviderKind::Ollama => Ok(self
+                .fetch_ollama_tags(&http)
+                .await?
+                .models
+                .into_iter()
+                .map(|m| m.name)
+                .collect()),
+            ProviderKind::OpenAiCompatible => Ok(self
+                .fetch_openai_models(&http)
+                .await?
+                .data
+                .into_iter()
+                .map(|m| m.id)
+                .collect()),
+        }
+    }
+
+    /// Chat models available on the server (embedding-only models filtered out)
+    pub async fn list_chat_models(&self) -> Result<Vec<ChatModel>> {
+        let http = reqwest::Client::new();
+        match self.config.kind {
+            ProviderKind::Ollama => self.list_ollama_chat_models(&http).await,
+            ProviderKind::OpenAiCompatible => Ok(self
+                .fetch_openai_models(&http)
+                .await?
+                .data
+                .into_iter()
+                .filter(|m| self.is_chat_model(&m.id))
+                .map(|m| ChatModel {
+                    name: m.id,
+                    size: 0,
+                    modified_at: m.created.map(|c| c.to_string()).unwrap_or_default(),
+                    family: m.owned_by,
+                    parameter_size: None,
+                    quantization_level: None,
+                    context_length: None,
+                    is_loaded: false,
+                })
+                .collect()),
+        }
+    }
+
+    /// Check if the provider is reachable and the embedding model is available
+    pub async fn status(&self) -> ProviderStatus {
+        let config = &self.config;
+
+        match self.list_model_names().await {
+            Ok(names) => {
+                let has_embedding_model = names
+                    .iter()
+                    .any(|name| name.contains(config.embedding_model.as_str()));
+
+                let chat_models_count =
+                    names.iter().filter(|name| self.is_chat_model(name)).count();
+
+                let message = match (has_embedding_model, config.kind) {
+                    (true, ProviderKind::Ollama) => "Ollama is ready".to_string(),
+                    (true, ProviderKind::OpenAiCompatible) => {
+                        format!("{} is ready", config.base_url)
+                    }
+                    (false, ProviderKind::Ollama) => format!(
+                        "Ollama is running but {0} is not installed. Run: ollama pull {0}",
+                        config.embedding_model
+                    ),
+                    (false, ProviderKind::OpenAiCompatible) => format!(
+                        "{} is running but does not list the embedding model {}",
+                        config.base_url, config.embedding_model
+                    ),
+                };
+
+                ProviderStatus {
+                    is_running: true,
+                    has_embedding_model,
+                    chat_models_count,
+                    message,
+                }
+            }
+            Err(e) => ProviderStatus {
+                is_running: false,
+                has_embedding_model: false,
+                chat_models_count: 0,
+                message: match config.kind {
+                    ProviderKind::Ollama => {
+                        "Ollama is not running. Start it with: ollama serve".to_string()
+                    }
+                    ProviderKind::OpenAiCompatible => e.to_string(),
+                },
+            },
+        }
+    }
+
+    /// Whether a model name looks like a chat model rather than an embedding model
+    pub fn is_chat_model(&self, name: &str) -> bool {
+        let name_lower = name.to_lowercase();
+        name_lower != self.config.embedding_model.to_lowercase()
+            && !EMBEDDING_MODELS.iter().any(|em| name_lower.contains(em))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+
+    async fn fetch_ollama_tags(&self, http: &reqwest::Client) -> Result<OllamaTagsResponse> {
+        self.get_json(http.get(self.url("/api/tags"))).await
+    }
+
+    async fn fetch_openai_models(&self, http: &reqwest::Client) -> Result<OpenAiModelsResponse> {
+        self.get_json(self.authorized(http.get(self.url("/models"))))
+            .await
+    }
+
+    /// Attach the API key (if any) as a bearer token
+    fn authorized(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.config.api_key.as_deref().filter(|k| !k.is_empty()) {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    /// Send a request and decode a successful JSON response
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let url = self.config.base_url.clone();
+        let response = request.send().await.map_err(|source| Error::Connection {
+            url: url.clone(),
+            source,
+        })?;
+
+        if !response.status().is_success() {
+            return Err(Error::InvalidResponse {
+                url,
+                message: format!("error status {}", response.status()),
+            });
+        }
+
+        response.json().await.map_err(|e| Error::InvalidResponse {
+            url,
+            message: e.to_string(),
+        })
+    }
+
+    async fn list_ollama_chat_models(&self, http: &reqwest::Client) -> Result<Vec<ChatModel>> {
+        let tags = self.fetch_ollama_tags(http).await?;
+
+        // Models currently loaded in memory; a failure here is not fatal
+        let loaded = self.fetch_ollama_loaded_models(http).await;
+
+        let chat_models: Vec<_> = tags
+            .models
+            .into_iter()
+            .filter(|m| self.is_chat_model(&m.name))
+            .collect();
+
+        // Look up context lengths concurrently
+        let context_lengths = futures::future::join_all(
+            chat_models
+                .iter()
+                .map(|m| self.fetch_ollama_context_length(http, &m.name)),
+        )
+        .await;
+
+        let models = chat_models
+            .into_iter()
+            .zip(context_lengths)
+            .map(|(m, context_length)| {
+                let details = m.details.unwrap_or_default();
+                ChatModel {
+                    is_loaded: loaded.contains(&m.name),
+                    name: m.name,
+                    size: m.size,
+                    modified_at: m.modified_at,
+                    family: details.family,
+                    parameter_size: details.parameter_size,
+                    quantization_level: details.quantization_level,
+                    context_length,
+                }
+            })
+            .collect();
+
+        Ok(models)
+    }
+
+    /// Names of the models Ollama currently has loaded (empty if /api/ps fails)
+    async fn fetch_ollama_loaded_models(&self, http: &reqwest::Client) -> HashSet<String> {
+        let response = match http.get(self.url("/api/ps")).send().await {
+            Ok(resp) if resp.status().is_success() => resp,
+            _ => return HashSet::new(),
+        };
+
+        response
+            .json::<OllamaPsResponse>()
+            .await
+            .map(|ps| ps.models.into_iter().map(|m| m.name).collect())
+            .unwrap_or_default()
+    }
+
+    /// Context window of a model from /api/show (None if unavailable)
+    async fn fetch_ollama_context_length(
+        &self,
+        http: &reqwest::Client,
+        model_name: &str,
+    ) -> Option<u64> {
+        let response = http
+            .post(self.url("/api/show"))
+            .json(&serde_json::json!({ "model": model_name }))
+            .send()
+            .await
+            .ok()?;
+
+        if !response.status().is_success() {
+            return None;
+        }
+
+        let show: OllamaShowResponse = response.json().await.ok()?;
+        context_length_from_model_info(&show.model_info?)
+    }
+}
+
+/// Embedding model of whichever backend is configured
+#[derive(Clone)]
+pub enum ProviderEmbeddingModel {
+    Ollama(ollama::EmbeddingModel),
+    OpenAi(openai::EmbeddingModel),
+}
+
+impl EmbeddingModel for ProviderEmbeddingModel {
+    const MAX_DOCUMENTS: usize = 1024;
+
+    fn ndims(&self) -> usize {
+        match self {
+            Self::Ollama(model) => model.ndims(),
+            Self::OpenAi(model) => model.ndims(),
+        }
+    }
+
+    async fn embed_texts(
+        &self,
+        texts: impl IntoIterator<Item = String> + Send,
+    ) -> std::result::Result<Vec<Embedding>, EmbeddingError> {
+        let texts: Vec<String> = texts.into_iter().collect();
+        match self {
+            Self::Ollama(model) => model.embed_texts(texts).await,
+            Self::OpenAi(model) => model.embed_texts(texts).await,
+        }
+    }
+}
+
+/// Response from the OpenAI-compatible /models endpoint
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiModelsResponse {
+    data: Vec<OpenAiModelInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiModelInfo {
+    id: String,
+    #[serde(default)]
+    created: Option<u64>,
+    #[serde(default)]
+    owned_by: Option<String>,
+}
+
+/// Split a streaming HTTP body into lines
+fn lines(response: reqwest::Response, url: String) -> BoxStream<'static, Result<String>> {
+    let state = (response.bytes_stream().boxed(), Vec::new(), false);
+
+    stream::unfold(state, move |(mut bytes, mut buffer, mut finished)| {
+        let url = url.clone();
+        async move {
+            loop {
+                if let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=pos).collect();
+                    let line = String::from_utf8_lossy(&line).trim().to_string();
+                    return Some((Ok(line), (bytes, buffer, finished)));
+                }
+
+                if finished {
+                    if buffer.is_empty() {
+                        return None;
+                    }
+                    let line = String::from_utf8_lossy(&buffer).trim().to_string();
+                    return Some((Ok(line), (bytes, Vec::new(), true)));
+                }
+
+                match bytes.next().await {
+                    Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                    Some(Err(source)) => {
+                        let error = Error::Connection { url, source };
+                        return Some((Err(error), (bytes, Vec::new(), true)));
+                    }
+                    None => finished = true,
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+/// Extract the text fragment from one line of a streaming chat response
+fn parse_stream_line(kind: ProviderKind, line: &str) -> Result<Option<String>> {
+    let payload = match kind {
+        // Ollama streams newline-delimited JSON objects
+        ProviderKind::Ollama => line,
+        // OpenAI-compatible servers stream Server-Sent Events
+        ProviderKind::OpenAiCompatible => match line.strip_prefix("data:") {
+            Some(data) if data.trim() == "[DONE]" => return Ok(None),
+            Some(data) => data.trim(),
+            None => return Ok(None),
+        },
+    };
+
+    if payload.is_empty() {
+        return Ok(None);
+    }
+
+    let value: serde_json::Value =
+        serde_json::from_str(payload).map_err(|e| Error::InvalidResponse {
+            url: "stream".to_string(),
+            message: e.to_string(),
+        })?;
+
+    if let Some(error) = value.get("error") {
+        return Err(Error::InvalidResponse {
+            url: "stream".to_string(),
+            message: error.to_string(),
+        });
+    }
+
+    let text = match kind {
+        ProviderKind::Ollama => value.pointer("/message/content"),
+        ProviderKind::OpenAiCompatible => value.pointer("/choices/0/delta/content"),
+    };
+
+    Ok(text
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.is_empty())
+        .map(str::to_string))
+}
+
+/// Find the "<architecture>.context_length" entry in Ollama's model_info map
+fn context_length_from_model_info(
+    model_info: &serde_json::Map<String, serde_json::Value>,
+) -> Option<u64> {
+    // Prefer the key for the declared architecture, fall back to any match
+    let architecture = model_info
+        .get("general.architecture")
+        .and_then(|v| v.as_str());
+
+    if let Some(arch) = architecture {
+        if let Some(len) = model_info
+            .get(&format!("{}.context_length", arch))
+            .and_then(|v| v.as_u64())
+        {
+            return Some(len);
+        }
+    }
+
+    model_info
+        .iter()
+        .find(|(key, _)| key.ends_with(".context_length"))
+        .and_then(|(_, v)| v.as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_settings() {
+        let dir = std::env::temp_dir().join(format!("cy-dog-provider-{}", std::process::id()));
+        assert_eq!(
+            ProviderConfig::load(&dir).unwrap().base_url,
+            DEFAULT_OLLAMA_URL
+        );
+
+        let config = ProviderConfig {
+            kind: ProviderKind::OpenAiCompatible,
+            base_url: "http://localhost:8080/v1".to_string(),
+            api_key: Some("secret".to_string()),
+            embedding_model: "bge-m3".to_string(),
+        };
+        config.save(&dir).unwrap();
+        let loaded = ProviderConfig::load(&dir).unwrap();
+        assert_eq!(loaded.kind, ProviderKind::OpenAiCompatible);
+        assert_eq!(loaded.base_url, config.base_url);
+        assert_eq!(loaded.api_key.as_deref(), Some("secret"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.join(SETTINGS_FILE_NAME))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_stream_line() {
+        let ollama = r#"{"message":{"role":"assistant","content":"Hel"},"done":false}"#;
+        assert_eq!(
+            parse_stream_line(ProviderKind::Ollama, ollama).unwrap(),
+            Some("Hel".to_string())
+        );
+
+        let openai = r#"data: {"choices":[{"delta":{"content":"lo"}}]}"#;
+        assert_eq!(
+            parse_stream_line(ProviderKind::OpenAiCompatible, openai).unwrap(),
+            Some("lo".to_string())
+        );
+        assert_eq!(
+            parse_stream_line(ProviderKind::OpenAiCompatible, "data: [DONE]").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_context_length_from_model_info() {
+        let info = serde_json::json!({
+            "general.architecture": "llama",
+            "llama.context_length": 131072,
+            "llama.embedding_length": 4096,
+        });
+        let info = info.as_object().unwrap();
+
+        assert_eq!(context_length_from_model_info(info), Some(131072));
+    }
+}
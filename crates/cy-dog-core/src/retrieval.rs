@@ -0,0 +1,75 @@
+use crate::error::Result;
+use crate::generation::generate_rag_response;
+use crate::index::{EmbeddableDocument, VectorIndex};
+use crate::provider::Provider;
+use crate::types::QueryResult;
+
+/// Number of similar documents to retrieve for context
+pub const TOP_K_RESULTS: usize = 5;
+
+/// Retrieve the most relevant rows for a question and answer it with the chat model
+pub async fn answer_question(
+    index: &VectorIndex,
+    provider: &Provider,
+    model_name: &str,
+    query: &str,
+    top_k: usize,
+) -> Result<QueryResult> {
+    // Search for relevant documents
+    let relevant_docs = index.search(query, top_k).await?;
+    
+    answer_from_documents(relevant_docs, provider, model_name, query).await
+}
+
+/// Search several named collections at once and keep the best `top_k` rows overall.
+///
+/// Each row's source is prefixed with its collection name ("Product A / interviews.csv")
+/// so citations say where it came from.
+pub async fn search_collections(
+    collections: &[(&str, &VectorIndex)],
+    query: &str,
+    top_k: usize,
+) -> Result<Vec<EmbeddableDocument>> {
+    let mut scored = Vec::new();
+    for (name, index) in collections {
+        for (score, mut doc) in index.search_scored(query, top_k, |_| true).await? {
+            doc.source_file = format!("{} / {}", name, doc.source_file);
+            scored.push((score, doc));
+        }
+    }
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    
+    Ok(scored.into_iter().take(top_k).map(|(_, doc)| doc).collect())
+}
+
+/// Like `answer_question`, but retrieving from several named collections
+pub async fn answer_across_collections(
+    collections: &[(&str, &VectorIndex)],
+    provider: &Provider,
+    model_name: &str,
+    query: &str,
+    top_k: usize,
+) -> Result<QueryResult> {
+    let relevant_docs = search_collections(collections, query, top_k).await?;
+    
+    answer_from_documents(relevant_docs, provider, model_name, query).await
+}
+
+async fn answer_from_documents(
+    relevant_docs: Vec<EmbeddableDocument>,
+    provider: &Provider,
+    model_name: &str,
+    query: &str,
+) -> Result<QueryResult> {
+    if relevant_docs.is_empty() {
+        return Ok(QueryResult {
+            answer: "No relevant information found in the indexed data.".to_string(),
+            sources: vec![],
+        });
+    }
+    
+    // Generate response
+    let (answer, sources) = generate_rag_response(query, relevant_docs, provider, model_name).await?;
+    
+    Ok(QueryResult { answer, sources })
+}
@@ -0,0 +1,385 @@
+//! Retrieval evaluation against a gold set of questions and the rows that
+//! should be retrieved for them.
+//!
+//! Each configuration (embedding model × top-k) is scored with recall@k,
+//! mean reciprocal rank and nDCG@k, using binary relevance.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::batch::write_with_json;
+use crate::error::{Error, Result};
+use crate::index::{EmbeddableDocument, VectorIndex};
+use crate::ingest::ingest_folder;
+use crate::provider::{Provider, ProviderConfig};
+use crate::schema::DatasetSchema;
+
+/// A row that should be retrieved for a gold question
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "ExpectedRowSpec")]
+pub struct ExpectedRow {
+    pub source_file: String,
+    /// 1-indexed data row, as shown in citations
+    pub row_number: usize,
+}
+
+/// Accepted spellings of an expected row: `"file.csv, Row 3"` or `{file, row}`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExpectedRowSpec {
+    Citation(String),
+    Fields { file: String, row: usize },
+}
+
+impl TryFrom<ExpectedRowSpec> for ExpectedRow {
+    type Error = String;
+
+    fn try_from(spec: ExpectedRowSpec) -> std::result::Result<Self, String> {
+        match spec {
+            ExpectedRowSpec::Fields { file, row } => Ok(Self {
+                source_file: file,
+                row_number: row,
+            }),
+            ExpectedRowSpec::Citation(citation) => {
+                let (file, row) = citation
+                    .rsplit_once(", Row ")
+                    .ok_or_else(|| format!("expected \"<file>, Row <n>\", got {:?}", citation))?;
+                let row_number = row
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid row number in {:?}", citation))?;
+                Ok(Self {
+                    source_file: file.trim().to_string(),
+                    row_number,
+                })
+            }
+        }
+    }
+}
+
+impl ExpectedRow {
+    fn matches(&self, doc: &EmbeddableDocument) -> bool {
+        self.source_file == doc.source_file && self.row_number == doc.row_number
+    }
+}
+
+/// A question and the rows a good retriever returns for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoldQuestion {
+    pub question: String,
+    pub expected: Vec<ExpectedRow>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GoldSetFile {
+    List(Vec<GoldQuestion>),
+    Keyed { questions: Vec<GoldQuestion> },
+}
+
+/// Read a gold set from YAML or JSON: a list of `{question, expected}` entries,
+/// optionally under a `questions:` key
+pub fn load_gold_set(path: &Path) -> Result<Vec<GoldQuestion>> {
+    let file = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|source| Error::Io {
+        path: file.clone(),
+        source,
+    })?;
+
+    // JSON is valid YAML, so one parser covers both
+    let parsed: GoldSetFile = serde_yaml::from_str(&text).map_err(|e| Error::Parse {
+        file,
+        row: None,
+        message: e.to_string(),
+    })?;
+
+    let questions = match parsed {
+        GoldSetFile::List(questions) | GoldSetFile::Keyed { questions } => questions,
+    };
+    Ok(questions
+        .into_iter()
+        .filter(|q| !q.question.trim().is_empty() && !q.expected.is_empty())
+        .collect())
+}
+
+/// Recall, MRR and nDCG for one ranked result list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetrievalMetrics {
+    pub recall: f64,
+    pub mrr: f64,
+    pub ndcg: f64,
+}
+
+impl RetrievalMetrics {
+    /// Score the first `k` results; `hits[i]` says whether result `i` is relevant
+    pub fn score(hits: &[bool], relevant: usize, k: usize) -> Self {
+        if relevant == 0 || k == 0 {
+            return Self::default();
+        }
+
+        let hits = &hits[..hits.len().min(k)];
+        let found = hits.iter().filter(|&&hit| hit).count();
+        let mrr = hits
+            .iter()
+            .position(|&hit| hit)
+            .map_or(0.0, |rank| 1.0 / (rank + 1) as f64);
+
+        let gain = |rank: usize| 1.0 / (rank as f64 + 2.0).log2();
+        let dcg: f64 = hits
+            .iter()
+            .enumerate()
+            .filter(|(_, hit)| **hit)
+            .map(|(rank, _)| gain(rank))
+            .sum();
+        let ideal: f64 = (0..relevant.min(k)).map(gain).sum();
+
+        Self {
+            recall: found as f64 / relevant as f64,
+            mrr,
+            ndcg: dcg / ideal,
+        }
+    }
+}
+
+/// Averaged metrics for one embedding model at one top-k
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetrievalScore {
+    pub embedding_model: String,
+    pub top_k: usize,
+    pub questions: usize,
+    #[serde(flatten)]
+    pub metrics: RetrievalMetrics,
+}
+
+/// What was retrieved for one gold question (at the largest top-k)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionRetrieval {
+    pub embedding_model: String,
+    pub question: String,
+    /// Citations of the retrieved rows, best first
+    pub retrieved: Vec<String>,
+    /// 1-indexed rank of the first expected row, if it was retrieved
+    pub first_hit: Option<usize>,
+}
+
+/// Full result of a retrieval evaluation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetrievalEvaluation {
+    /// Seconds since the Unix epoch when the run finished
+    pub generated_at: u64,
+    pub data_folder: String,
+    pub scores: Vec<RetrievalScore>,
+    pub questions: Vec<QuestionRetrieval>,
+}
+
+/// Score an existing index at each of the given top-k values
+pub async fn evaluate_index(
+    index: &VectorIndex,
+    gold: &[GoldQuestion],
+    top_ks: &[usize],
+) -> Result<(Vec<RetrievalScore>, Vec<QuestionRetrieval>)> {
+    let max_k = top_ks.iter().copied().max().unwrap_or(0);
+    let mut totals = vec![RetrievalMetrics::default(); top_ks.len()];
+    let mut questions = Vec::with_capacity(gold.len());
+
+    for entry in gold {
+        // One search at the largest k covers every smaller k. A row takes
+        // one rank however many of its chunks are retrieved, so rank every
+        // row before keeping the best max_k
+        let mut seen = HashSet::new();
+        let results: Vec<EmbeddableDocument> = index
+            .search(&entry.question, index.len())
+            .await?
+            .into_iter()
+            .filter(|doc| seen.insert(doc.citation()))
+            .take(max_k)
+            .collect();
+        let hits: Vec<bool> = results
+            .iter()
+            .map(|doc| entry.expected.iter().any(|e| e.matches(doc)))
+            .collect();
+
+        for (total, &k) in totals.iter_mut().zip(top_ks) {
+            let metrics = RetrievalMetrics::score(&hits, entry.expected.len(), k);
+            total.recall += metrics.recall;
+            total.mrr += metrics.mrr;
+            total.ndcg += metrics.ndcg;
+        }
+
+        questions.push(QuestionRetrieval {
+            embedding_model: index.embedding_model_name().to_string(),
+            question: entry.question.clone(),
+            retrieved: results.iter().map(EmbeddableDocument::citation).collect(),
+            first_hit: hits.iter().position(|&hit| hit).map(|rank| rank + 1),
+        });
+    }
+
+    let count = gold.len().max(1) as f64;
+    let scores = totals
+        .into_iter()
+        .zip(top_ks)
+        .map(|(total, &top_k)| RetrievalScore {
+            embedding_model: index.embedding_model_name().to_string(),
+            top_k,
+            questions: gold.len(),
+            metrics: RetrievalMetrics {
+                recall: total.recall / count,
+                mrr: total.mrr / count,
+                ndcg: total.ndcg / count,
+            },
+        })
+        .collect();
+
+    Ok((scores, questions))
+}
+
+/// Index `data_folder` (with the given column roles) once per embedding model
+/// and score each at every top-k
+pub async fn evaluate_retrieval(
+    data_folder: &str,
+    schema: &DatasetSchema,
+    gold: &[GoldQuestion],
+    config: &ProviderConfig,
+    embedding_models: &[String],
+    top_ks: &[usize],
+) -> Result<RetrievalEvaluation> {
+    let mut scores = Vec::new();
+    let mut questions = Vec::new();
+
+    for embedding_model in embedding_models {
+        let provider = Provider::new(ProviderConfig {
+            embedding_model: embedding_model.clone(),
+            ..config.clone()
+        });
+
+        let index = ingest_folder(data_folder, &provider, &mut schema.clone())
+            .await?
+            .index
+            .ok_or(Error::NoDocuments)?;
+
+        let (model_scores, model_questions) = evaluate_index(&index, gold, top_ks).await?;
+        scores.extend(model_scores);
+        questions.extend(model_questions);
+    }
+
+    Ok(RetrievalEvaluation {
+        generated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        data_folder: data_folder.to_string(),
+        scores,
+        questions,
+    })
+}
+
+/// Render the comparison table (plus misses) as Markdown
+pub fn render_retrieval_table(evaluation: &RetrievalEvaluation) -> String {
+    let mut out = String::new();
+    out.push_str("# Retrieval Evaluation\n\n");
+    out.push_str(&format!(
+        "Data: `{}` · Questions: {}\n\n",
+        evaluation.data_folder,
+        evaluation.scores.first().map_or(0, |s| s.questions)
+    ));
+
+    out.push_str("| Embedding model | k | Recall@k | MRR@k | nDCG@k |\n");
+    out.push_str("|---|---:|---:|---:|---:|\n");
+    for score in &evaluation.scores {
+        out.push_str(&format!(
+            "| {} | {} | {:.3} | {:.3} | {:.3} |\n",
+            score.embedding_model,
+            score.top_k,
+            score.metrics.recall,
+            score.metrics.mrr,
+            score.metrics.ndcg
+        ));
+    }
+
+    let misses: Vec<&QuestionRetrieval> = evaluation
+        .questions
+        .iter()
+        .filter(|q| q.first_hit.is_none())
+        .collect();
+    if !misses.is_empty() {
+        out.push_str("\n## Questions with no expected row retrieved\n\n");
+        for miss in misses {
+            out.push_str(&format!(
+                "- `{}`: {}\n",
+                miss.embedding_model, miss.question
+            ));
+        }
+    }
+
+    out
+}
+
+/// Where an evaluation report was written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationOutput {
+    pub report_path: String,
+    pub json_path: String,
+}
+
+/// Write the Markdown table plus a JSON file with every score and retrieval
+pub fn write_retrieval_report(
+    evaluation: &RetrievalEvaluation,
+    output_path: &Path,
+) -> Result<EvaluationOutput> {
+    write_evaluation(evaluation, &render_retrieval_table(evaluation), output_path)
+}
+
+/// Write a rendered Markdown report and the raw results next to it as JSON
+pub(crate) fn write_evaluation<T: Serialize>(
+    results: &T,
+    markdown: &str,
+    output_path: &Path,
+) -> Result<EvaluationOutput> {
+    let (report_path, json_path) = write_with_json(results, markdown, output_path, "md")?;
+    Ok(EvaluationOutput {
+        report_path: report_path.display().to_string(),
+        json_path: json_path.display().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_and_expected_row_formats() {
+        // Relevant rows at ranks 2 and 4, three relevant rows in total
+        let hits = [false, true, false, true, false];
+
+        let at_1 = RetrievalMetrics::score(&hits, 3, 1);
+        assert_eq!(at_1, RetrievalMetrics::default());
+
+        let at_5 = RetrievalMetrics::score(&hits, 3, 5);
+        assert!((at_5.recall - 2.0 / 3.0).abs() < 1e-9);
+        assert!((at_5.mrr - 0.5).abs() < 1e-9);
+        let dcg = 1.0 / 3f64.log2() + 1.0 / 5f64.log2();
+        let ideal = 1.0 + 1.0 / 3f64.log2() + 0.5;
+        assert!((at_5.ndcg - dcg / ideal).abs() < 1e-9);
+
+        let gold: Vec<GoldQuestion> = serde_yaml::from_str(
+            "- question: Who counts inventory?\n  expected:\n    - interviews.csv, Row 2\n    - { file: other.csv, row: 7 }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            gold[0].expected,
+            vec![
+                ExpectedRow {
+                    source_file: "interviews.csv".to_string(),
+                    row_number: 2
+                },
+                ExpectedRow {
+                    source_file: "other.csv".to_string(),
+                    row_number: 7
+                },
+            ]
+        );
+    }
+}
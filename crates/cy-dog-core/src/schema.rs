@@ -0,0 +1,369 @@
+//! Column roles: which spreadsheet columns are embedded, which are only kept
+//! as filterable metadata and which are dropped.
+//!
+//! Roles are configured per dataset (optionally overridden per file) and
+//! auto-detected from the header and sample values the first time a column
+//! is seen. Workbook sheets are included or excluded the same way, and
+//! numeric columns can be given a number format.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cells::NumberFormat;
+use crate::error::{Error, Result};
+
+/// Rows looked at when detecting a column's role
+pub const SAMPLE_ROWS: usize = 50;
+
+/// How a column is used when its rows are indexed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnRole {
+    /// Embedded as "Header: value"
+    Text,
+    /// Kept in the row's metadata for filtering, not embedded
+    Metadata,
+    /// Who was interviewed: embedded and kept in the metadata
+    Interviewee,
+    /// When the interview happened: kept in the metadata, not embedded
+    Date,
+    /// Dropped entirely
+    Ignored,
+}
+
+impl ColumnRole {
+    pub const ALL: [ColumnRole; 5] = [
+        ColumnRole::Text,
+        ColumnRole::Metadata,
+        ColumnRole::Interviewee,
+        ColumnRole::Date,
+        ColumnRole::Ignored,
+    ];
+
+    /// Whether the value is part of the embedded content
+    pub fn is_embedded(self) -> bool {
+        matches!(self, ColumnRole::Text | ColumnRole::Interviewee)
+    }
+
+    /// Whether the value is kept in the row's metadata
+    pub fn is_metadata(self) -> bool {
+        matches!(
+            self,
+            ColumnRole::Metadata | ColumnRole::Interviewee | ColumnRole::Date
+        )
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColumnRole::Text => "text",
+            ColumnRole::Metadata => "metadata",
+            ColumnRole::Interviewee => "interviewee",
+            ColumnRole::Date => "date",
+            ColumnRole::Ignored => "ignored",
+        }
+    }
+}
+
+impl fmt::Display for ColumnRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ColumnRole {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        ColumnRole::ALL
+            .into_iter()
+            .find(|role| role.as_str() == s)
+            .ok_or_else(|| {
+                Error::InvalidSetting(format!(
+                    "Unknown column role \"{}\" (expected one of: text, metadata, interviewee, date, ignored)",
+                    s
+                ))
+            })
+    }
+}
+
+/// Column roles of a dataset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatasetSchema {
+    /// Roles by column header, for every file
+    #[serde(default)]
+    pub columns: BTreeMap<String, ColumnRole>,
+    /// Overrides for single files, by file name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, BTreeMap<String, ColumnRole>>,
+    /// Whether each workbook sheet is indexed, by file name then sheet name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sheets: BTreeMap<String, BTreeMap<String, bool>>,
+    /// How the numbers of workbook columns are written, by column header
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub number_formats: BTreeMap<String, NumberFormat>,
+}
+
+impl DatasetSchema {
+    /// The configured role of a column in a file, if any
+    pub fn role(&self, file: &str, column: &str) -> Option<ColumnRole> {
+        self.files
+            .get(file)
+            .and_then(|columns| columns.get(column))
+            .or_else(|| self.columns.get(column))
+            .copied()
+    }
+
+    /// Set a column's role for the whole dataset, or only for `file`
+    pub fn set_role(&mut self, file: Option<&str>, column: &str, role: ColumnRole) {
+        let columns = match file {
+            Some(file) => self.files.entry(file.to_string()).or_default(),
+            None => &mut self.columns,
+        };
+        columns.insert(column.to_string(), role);
+    }
+
+    /// Include or exclude one sheet of a workbook
+    pub fn set_sheet_included(&mut self, file: &str, sheet: &str, included: bool) {
+        self.sheets
+            .entry(file.to_string())
+            .or_default()
+            .insert(sheet.to_string(), included);
+    }
+
+    /// How a column's numbers are written (plain unless configured)
+    pub fn number_format(&self, column: &str) -> NumberFormat {
+        self.number_formats.get(column).copied().unwrap_or_default()
+    }
+
+    /// Set how a column's numbers are written; the plain format clears it
+    pub fn set_number_format(&mut self, column: &str, format: NumberFormat) {
+        if format.is_default() {
+            self.number_formats.remove(column);
+        } else {
+            self.number_formats.insert(column.to_string(), format);
+        }
+    }
+
+    /// Whether a workbook sheet is indexed, detecting (and recording) it for
+    /// sheets not seen before
+    pub fn resolve_sheet(&mut self, file: &str, sheet: &str, hidden: bool) -> bool {
+        *self
+            .sheets
+            .entry(file.to_string())
+            .or_default()
+            .entry(sheet.to_string())
+            .or_insert_with(|| detect_sheet(sheet, hidden))
+    }
+
+    /// Roles of a file's columns, detecting (and recording) those not seen before.
+    /// `rows` are the file's data rows; only the first `SAMPLE_ROWS` are looked at.
+    pub fn resolve(
+        &mut self,
+        file: &str,
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> Vec<ColumnRole> {
+        let sample = &rows[..rows.len().min(SAMPLE_ROWS)];
+
+        headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                if let Some(role) = self.role(file, header) {
+                    return role;
+                }
+                let values: Vec<&str> = sample
+                    .iter()
+                    .filter_map(|row| row.get(i).map(String::as_str))
+                    .collect();
+                let role = detect_role(header, &values);
+                self.columns.insert(header.clone(), role);
+                role
+            })
+            .collect()
+    }
+}
+
+/// Guess a column's role from its header and some of its values
+pub fn detect_role(header: &str, values: &[&str]) -> ColumnRole {
+    let name = header.trim().to_lowercase();
+    let words: Vec<&str> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let has_word = |candidates: &[&str]| words.iter().any(|word| candidates.contains(word));
+    let filled: Vec<&str> = values
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .collect();
+
+    if has_word(&["interviewer", "interviewers", "initials"]) {
+        return ColumnRole::Ignored;
+    }
+    if name == "#" || matches!(words.last(), Some(&("id" | "uuid" | "guid"))) {
+        return ColumnRole::Metadata;
+    }
+    if is_date_label(&name, &words)
+        || (!filled.is_empty() && filled.iter().all(|value| looks_like_date(value)))
+    {
+        return ColumnRole::Date;
+    }
+    if has_word(&["interviewee", "participant", "respondent"])
+        || matches!(
+            name.as_str(),
+            "name" | "full name" | "customer" | "customer name"
+        )
+    {
+        return ColumnRole::Interviewee;
+    }
+    if !filled.is_empty() && filled.iter().all(|value| value.parse::<f64>().is_ok()) {
+        return ColumnRole::Metadata;
+    }
+    ColumnRole::Text
+}
+
+/// Guess whether a sheet holds interview data: hidden sheets and helper
+/// tabs such as "Lookup" or "Pivot" are left out
+pub fn detect_sheet(name: &str, hidden: bool) -> bool {
+    const HELPER_WORDS: &[&str] = &[
+        "lookup",
+        "lookups",
+        "pivot",
+        "chart",
+        "charts",
+        "dropdown",
+        "dropdowns",
+        "settings",
+        "config",
+        "helper",
+        "instructions",
+        "readme",
+    ];
+
+    let name = name.to_lowercase();
+    let is_helper = name
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| HELPER_WORDS.contains(&word));
+    !hidden && !is_helper
+}
+
+/// A short header naming a date ("Date", "created_at", "Interview date",
+/// "Last updated"); longer headers that mention one, such as "How much time
+/// do you spend on scheduling?", are questions whose answers are text
+fn is_date_label(name: &str, words: &[&str]) -> bool {
+    const DATE_WORDS: &[&str] = &["date", "timestamp", "created", "updated", "submitted"];
+
+    if name == "time" {
+        return true;
+    }
+    if words.len() > 3 || name.contains('?') {
+        return false;
+    }
+    [words.first(), words.last()]
+        .into_iter()
+        .flatten()
+        .any(|word| DATE_WORDS.contains(word))
+}
+
+/// "2024-03-02", "02/03/2024", "2024-03-02T14:30:00" and the like
+fn looks_like_date(value: &str) -> bool {
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '-' | '/' | '.' | ':' | ' ' | 'T' | 'Z'))
+    {
+        return false;
+    }
+
+    let groups: Vec<&str> = value
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|group| !group.is_empty())
+        .collect();
+    let separators = value.chars().filter(|c| matches!(c, '-' | '/')).count();
+
+    groups.len() >= 3
+        && separators >= 2
+        && groups[..3].iter().any(|group| group.len() == 4)
+        && groups[..3].iter().all(|group| group.len() <= 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_override_roles() {
+        assert_eq!(
+            detect_role("Respondent ID", &["r-17"]),
+            ColumnRole::Metadata
+        );
+        assert_eq!(detect_role("Interviewer", &["DM"]), ColumnRole::Ignored);
+        assert_eq!(
+            detect_role("When", &["2024-03-02", "02/03/2024 14:30"]),
+            ColumnRole::Date
+        );
+        assert_eq!(detect_role("created_at", &[]), ColumnRole::Date);
+        assert_eq!(
+            detect_role("Submitted at", &["yesterday"]),
+            ColumnRole::Date
+        );
+        assert_eq!(detect_role("Name", &["Alice"]), ColumnRole::Interviewee);
+        assert_eq!(detect_role("Score", &["4", "3.5"]), ColumnRole::Metadata);
+        assert_eq!(
+            detect_role("Pain Point", &["Scheduling takes hours"]),
+            ColumnRole::Text
+        );
+
+        let mut schema = DatasetSchema::default();
+        schema.set_role(Some("b.csv"), "Notes", ColumnRole::Ignored);
+        let headers = vec!["Notes".to_string(), "Interviewer".to_string()];
+        let rows = vec![vec!["Long answer".to_string(), "DM".to_string()]];
+
+        assert_eq!(
+            schema.resolve("a.csv", &headers, &rows),
+            vec![ColumnRole::Text, ColumnRole::Ignored]
+        );
+        assert_eq!(
+            schema.resolve("b.csv", &headers, &rows),
+            vec![ColumnRole::Ignored, ColumnRole::Ignored]
+        );
+        // Detected roles are remembered for the dataset
+        assert_eq!(schema.columns.get("Notes"), Some(&ColumnRole::Text));
+        assert_eq!("Date".parse::<ColumnRole>().unwrap(), ColumnRole::Date);
+    }
+
+    #[test]
+    fn test_questions_mentioning_time_are_text() {
+        for header in [
+            "How much time do you spend on scheduling?",
+            "What have you created to work around this?",
+            "Time spent",
+        ] {
+            assert_eq!(
+                detect_role(header, &["About two hours a week"]),
+                ColumnRole::Text,
+                "{}",
+                header
+            );
+        }
+    }
+
+    #[test]
+    fn test_sheet_selection() {
+        let mut schema = DatasetSchema::default();
+        assert!(schema.resolve_sheet("calls.xlsx", "Interviews", false));
+        assert!(!schema.resolve_sheet("calls.xlsx", "Lookup", false));
+        assert!(!schema.resolve_sheet("calls.xlsx", "Pivot Table 1", false));
+        assert!(!schema.resolve_sheet("calls.xlsx", "Archive", true));
+
+        // Choices are remembered and can be overridden
+        schema.set_sheet_included("calls.xlsx", "Lookup", true);
+        schema.set_sheet_included("calls.xlsx", "Interviews", false);
+        assert!(schema.resolve_sheet("calls.xlsx", "Lookup", false));
+        assert!(!schema.resolve_sheet("calls.xlsx", "Interviews", false));
+        assert_eq!(schema.sheets["calls.xlsx"].len(), 4);
+    }
+}
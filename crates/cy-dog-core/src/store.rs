@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::index::{IndexedDocument, VectorIndex};
+use crate::provider::Provider;
+
+/// Application identifier, shared with the desktop app's data directory
+pub const APP_IDENTIFIER: &str = "com.adman-voids.cy-dog";
+
+/// File name of the persisted index inside the data directory
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// Bumped whenever the on-disk format changes incompatibly
+const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// Directory where Cy Dog keeps its data (same location as Tauri's app data dir)
+pub fn default_data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_IDENTIFIER)
+}
+
+/// On-disk representation of an index
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedIndex {
+    version: u32,
+    data_folder: Option<String>,
+    embedding_model: String,
+    documents: Vec<IndexedDocument>,
+}
+
+/// An index read back from disk
+pub struct LoadedIndex {
+    pub index: VectorIndex,
+    /// Folder the index was built from
+    pub data_folder: Option<String>,
+}
+
+/// Reads and writes the persisted vector index
+#[derive(Debug, Clone)]
+pub struct IndexStore {
+    path: PathBuf,
+}
+
+impl IndexStore {
+    /// Store backed by a specific file
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Store in the default data directory, shared by the desktop app and CLI
+    pub fn default_location() -> Self {
+        Self::new(default_data_dir().join(INDEX_FILE_NAME))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the index (and the folder it was built from) to disk
+    pub fn save(&self, index: &VectorIndex, data_folder: Option<&str>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|source| self.io_error(source))?;
+        }
+
+        let saved = SavedIndex {
+            version: INDEX_FORMAT_VERSION,
+            data_folder: data_folder.map(str::to_string),
+            embedding_model: index.embedding_model_name().to_string(),
+            documents: index.documents().to_vec(),
+        };
+
+        let json = serde_json::to_vec(&saved).map_err(|e| self.corrupt(e.to_string()))?;
+
+        // Write to a temporary file first so a crash never leaves a truncated index
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).map_err(|source| self.io_error(source))?;
+        fs::rename(&tmp_path, &self.path).map_err(|source| self.io_error(source))
+    }
+
+    /// Read the index from disk (None if nothing has been saved yet)
+    pub fn load(&self, provider: &Provider) -> Result<Option<LoadedIndex>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(&self.path).map_err(|source| self.io_error(source))?;
+        let saved: SavedIndex =
+            serde_json::from_slice(&bytes).map_err(|e| self.corrupt(e.to_string()))?;
+
+        if saved.version != INDEX_FORMAT_VERSION {
+            return Err(self.corrupt(format!(
+                "unsupported index version {} (expected {})",
+                saved.version, INDEX_FORMAT_VERSION
+            )));
+        }
+
+        // Query embeddings must come from the same model as the stored ones
+        let configured = &provider.config().embedding_model;
+        if &saved.embedding_model != configured {
+            return Err(Error::EmbeddingModelMismatch {
+                indexed: saved.embedding_model,
+                configured: configured.clone(),
+            });
+        }
+
+        Ok(Some(LoadedIndex {
+            index: VectorIndex::from_embedded(saved.documents, provider),
+            data_folder: saved.data_folder,
+        }))
+    }
+
+    /// Delete the persisted index if there is one
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(source) => Err(self.io_error(source)),
+        }
+    }
+
+    fn io_error(&self, source: std::io::Error) -> Error {
+        Error::Io {
+            path: self.path.display().to_string(),
+            source,
+        }
+    }
+
+    fn corrupt(&self, message: String) -> Error {
+        Error::CorruptIndex {
+            path: self.path.display().to_string(),
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::EmbeddableDocument;
+    use crate::provider::ProviderConfig;
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let provider = Provider::new(ProviderConfig::default());
+        let documents = vec![IndexedDocument {
+            document: EmbeddableDocument {
+                id: "doc_0".to_string(),
+                content: "From a.csv, Row 1: Pain: onboarding".to_string(),
+                source_file: "a.csv".to_string(),
+                row_number: 1,
+                metadata: Default::default(),
+                chunk: None,
+                location: None,
+            },
+            embedding: vec![0.1, 0.2, 0.3],
+        }];
+        let index = VectorIndex::from_embedded(documents, &provider);
+
+        let path = std::env::temp_dir().join(format!("cy-dog-store-{}.json", std::process::id()));
+        let store = IndexStore::new(&path);
+        store.save(&index, Some("/data/interviews")).unwrap();
+
+        let loaded = store.load(&provider).unwrap().unwrap();
+        assert_eq!(loaded.index.len(), 1);
+        assert_eq!(loaded.data_folder.as_deref(), Some("/data/interviews"));
+        assert_eq!(loaded.index.documents()[0].document.id, "doc_0");
+
+        store.clear().unwrap();
+        assert!(store.load(&provider).unwrap().is_none());
+    }
+}
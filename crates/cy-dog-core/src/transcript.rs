@@ -0,0 +1,269 @@
+//! Call transcripts exported by meeting tools as WebVTT (`.vtt`) or SubRip
+//! (`.srt`) subtitles.
+//!
+//! Cues are grouped into speaker turns (or, when no speakers are labelled,
+//! fixed time windows). Each group becomes a document cited by file, start
+//! time and speaker, e.g. "call-2024-03-02.vtt @ 00:14:32 (Speaker: Customer)".
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::cells::CellValue;
+use crate::chunking::chunk_text;
+use crate::error::Result;
+use crate::loader::{CsvDocument, Location};
+use crate::notes::read_text;
+
+/// Longest speaker turn, in seconds; longer monologues start a new document
+const MAX_TURN_SECS: u64 = 120;
+
+/// Length of a group, in seconds, when the transcript has no speaker labels
+const WINDOW_SECS: u64 = 60;
+
+/// Longest "Name: " prefix taken as a speaker label
+const MAX_SPEAKER_WORDS: usize = 4;
+
+/// One subtitle cue
+#[derive(Debug, Clone, PartialEq)]
+struct Cue {
+    start_ms: u64,
+    end_ms: u64,
+    speaker: Option<String>,
+    text: String,
+}
+
+/// Parse a `.vtt` or `.srt` transcript into speaker turns or time windows
+pub(crate) fn parse_transcript_file(
+    file_path: &Path,
+    filename: &str,
+    doc_id: &mut usize,
+) -> Result<Vec<CsvDocument>> {
+    let text = read_text(file_path)?;
+    Ok(turn_documents(
+        filename,
+        group_cues(parse_cues(&text)),
+        doc_id,
+    ))
+}
+
+/// Cues of a WebVTT or SubRip file; both are blocks separated by blank lines
+/// with a "start --> end" timing line followed by the cue text
+fn parse_cues(text: &str) -> Vec<Cue> {
+    let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+
+    for block in text.split("\n\n") {
+        let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+        // Header, NOTE, STYLE and REGION blocks have no timing line
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+        let Some((start_ms, end_ms)) = parse_timing(timing) else {
+            continue;
+        };
+
+        let mut speaker = None;
+        let mut parts = Vec::new();
+        for line in lines {
+            let (voice, line) = strip_tags(line);
+            let (label, line) = match speaker_label(&line) {
+                Some((label, rest)) => (Some(label.to_string()), rest.to_string()),
+                None => (None, line),
+            };
+            if speaker.is_none() {
+                speaker = voice.or(label);
+            }
+            if !line.trim().is_empty() {
+                parts.push(line.trim().to_string());
+            }
+        }
+
+        if !parts.is_empty() {
+            cues.push(Cue {
+                start_ms,
+                end_ms,
+                speaker,
+                text: parts.join(" "),
+            });
+        }
+    }
+
+    cues
+}
+
+/// "00:14:32.120 --> 00:14:35.000 align:start" -> (start, end) in milliseconds
+fn parse_timing(line: &str) -> Option<(u64, u64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start.trim())?, parse_timestamp(end)?))
+}
+
+/// "01:02:03.456" (VTT), "01:02:03,456" (SRT) or "02:03.456" -> milliseconds
+fn parse_timestamp(value: &str) -> Option<u64> {
+    let (clock, millis) = value.split_once(['.', ',']).unwrap_or((value, "0"));
+    let mut seconds = 0;
+    for field in clock.split(':') {
+        seconds = seconds * 60 + field.parse::<u64>().ok()?;
+    }
+    Some(seconds * 1000 + millis.parse::<u64>().ok()?)
+}
+
+/// Remove markup such as `<i>` and `<c.yellow>`, returning the `<v Speaker>` voice if any
+fn strip_tags(line: &str) -> (Option<String>, String) {
+    let mut voice = None;
+    let mut text = String::new();
+    let mut rest = line;
+
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            rest = &rest[open..];
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        if let Some(name) = tag.strip_prefix('v').filter(|t| t.starts_with([' ', '.'])) {
+            // "<v Roger>" or "<v.loud Roger>"
+            let name = name.split_once(' ').map_or("", |(_, name)| name).trim();
+            if voice.is_none() && !name.is_empty() {
+                voice = Some(name.to_string());
+            }
+        }
+        rest = &rest[open + close + 1..];
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    (voice, text)
+}
+
+/// "Customer: I tried..." -> ("Customer", "I tried...")
+fn speaker_label(line: &str) -> Option<(&str, &str)> {
+    let (label, rest) = line.split_once(": ")?;
+    let label = label.trim();
+    let words = label.split_whitespace().count();
+    let looks_like_name = label.chars().next().is_some_and(char::is_alphabetic)
+        && !label.contains(['.', ',', '?', '!', '"']);
+    (looks_like_name && (1..=MAX_SPEAKER_WORDS).contains(&words)).then_some((label, rest))
+}
+
+/// Consecutive cues by the same speaker, capped in length; fixed windows
+/// when no cue names its speaker
+fn group_cues(cues: Vec<Cue>) -> Vec<Cue> {
+    let has_speakers = cues.iter().any(|cue| cue.speaker.is_some());
+    let limit_ms = if has_speakers {
+        MAX_TURN_SECS
+    } else {
+        WINDOW_SECS
+    } * 1000;
+    let mut groups: Vec<Cue> = Vec::new();
+
+    for cue in cues {
+        match groups.last_mut() {
+            Some(group)
+                if group.speaker == cue.speaker && cue.start_ms < group.start_ms + limit_ms =>
+            {
+                group.end_ms = group.end_ms.max(cue.end_ms);
+                group.text.push(' ');
+                group.text.push_str(&cue.text);
+            }
+            _ => groups.push(cue),
+        }
+    }
+
+    groups
+}
+
+/// Number turns from 1 and turn each (or each chunk of a long one) into a document
+fn turn_documents(filename: &str, turns: Vec<Cue>, doc_id: &mut usize) -> Vec<CsvDocument> {
+    let mut documents = Vec::new();
+
+    for (i, turn) in turns.into_iter().enumerate() {
+        let start = format_timestamp(turn.start_ms);
+        let prefix = match &turn.speaker {
+            Some(speaker) => format!("From {} @ {} (Speaker: {}): ", filename, start, speaker),
+            None => format!("From {} @ {}: ", filename, start),
+        };
+
+        let mut metadata = BTreeMap::new();
+        if let Some(speaker) = &turn.speaker {
+            metadata.insert("Speaker".to_string(), CellValue::from(speaker.as_str()));
+        }
+        metadata.insert("Start".to_string(), CellValue::from(start.as_str()));
+        metadata.insert(
+            "End".to_string(),
+            CellValue::from(format_timestamp(turn.end_ms)),
+        );
+
+        for (content, chunk) in chunk_text(&prefix, &turn.text) {
+            documents.push(CsvDocument {
+                id: format!("doc_{}", *doc_id),
+                content,
+                source_file: filename.to_string(),
+                row_number: i + 1,
+                metadata: metadata.clone(),
+                chunk,
+                location: Some(Location::Transcript {
+                    start: start.clone(),
+                    speaker: turn.speaker.clone(),
+                }),
+            });
+            *doc_id += 1;
+        }
+    }
+
+    documents
+}
+
+/// Milliseconds -> "HH:MM:SS"
+fn format_timestamp(ms: u64) -> String {
+    let seconds = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vtt_cues_are_grouped_into_speaker_turns() {
+        let vtt = "\u{feff}WEBVTT\n\nNOTE exported by the meeting tool\n\n1\n00:14:32.120 --> 00:14:35.000\n<v Customer>We export everything</v>\n\n2\n00:14:35.000 --> 00:14:38.500 align:start\n<v Customer>to <i>Excel</i> &amp; email it.</v>\n\n3\n00:14:39.000 --> 00:14:41.000\nInterviewer: Why not share a link?\n";
+
+        let turns = group_cues(parse_cues(vtt));
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].speaker.as_deref(), Some("Customer"));
+        assert_eq!(turns[0].text, "We export everything to Excel & email it.");
+        assert_eq!(turns[1].speaker.as_deref(), Some("Interviewer"));
+        assert_eq!(turns[1].text, "Why not share a link?");
+
+        let mut doc_id = 0;
+        let docs = turn_documents("call-2024-03-02.vtt", turns, &mut doc_id);
+        assert!(docs[0]
+            .content
+            .starts_with("From call-2024-03-02.vtt @ 00:14:32 (Speaker: Customer): "));
+        assert_eq!(docs[0].metadata["End"], "00:14:38");
+        assert_eq!(docs[1].row_number, 2);
+    }
+
+    #[test]
+    fn test_srt_without_speakers_uses_time_windows() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:04,000\r\nHello and thanks for joining.\r\n\r\n2\r\n00:00:30,000 --> 00:00:33,000\r\nSo, how do you plan shifts?\r\n\r\n3\r\n00:01:05,000 --> 00:01:09,000\r\nMostly on paper.\r\n";
+
+        let windows = group_cues(parse_cues(srt));
+        assert_eq!(windows.len(), 2);
+        assert_eq!(
+            windows[0].text,
+            "Hello and thanks for joining. So, how do you plan shifts?"
+        );
+        assert_eq!(windows[1].start_ms, 65_000);
+        assert_eq!(windows[1].speaker, None);
+    }
+}
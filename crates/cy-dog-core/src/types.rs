@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::dialect::CsvDialect;
+
+/// A chat model offered by the provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatModel {
+    pub name: String,
+    pub size: u64,
+    pub modified_at: String,
+    /// Model family reported by the provider (e.g. "llama", "qwen2")
+    pub family: Option<String>,
+    /// Human-readable parameter count (e.g. "8.0B")
+    pub parameter_size: Option<String>,
+    /// Quantization level (e.g. "Q4_K_M")
+    pub quantization_level: Option<String>,
+    /// Maximum context window in tokens (Ollama reports it in /api/show)
+    pub context_length: Option<u64>,
+    /// Whether the model is currently loaded in memory (Ollama's /api/ps)
+    pub is_loaded: bool,
+}
+
+/// Response from Ollama /api/tags endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaTagsResponse {
+    pub models: Vec<OllamaModelInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaModelInfo {
+    pub name: String,
+    pub size: u64,
+    pub modified_at: String,
+    #[serde(default)]
+    pub details: Option<OllamaModelDetails>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OllamaModelDetails {
+    pub family: Option<String>,
+    pub parameter_size: Option<String>,
+    pub quantization_level: Option<String>,
+}
+
+/// Response from Ollama /api/ps endpoint (models currently loaded in memory)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaPsResponse {
+    pub models: Vec<OllamaRunningModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaRunningModel {
+    pub name: String,
+}
+
+/// Response from Ollama /api/show endpoint (only the fields we use)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaShowResponse {
+    #[serde(default)]
+    pub model_info: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Ingestion result returned to frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestResult {
+    pub success: bool,
+    pub documents_ingested: usize,
+    pub files_processed: usize,
+    pub message: String,
+    /// Content that was skipped, e.g. PDF pages without a text layer
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Encoding and delimiter each CSV file was read with, by file name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dialects: BTreeMap<String, CsvDialect>,
+}
+
+/// RAG query result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryResult {
+    pub answer: String,
+    pub sources: Vec<String>,
+}
+
+/// Reachability of the provider and availability of the embedding model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderStatus {
+    pub is_running: bool,
+    pub has_embedding_model: bool,
+    pub chat_models_count: usize,
+    pub message: String,
+}
@@ -0,0 +1,237 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use cy_dog_core::ProviderConfig;
+
+/// Errors returned by Tauri commands and the local HTTP API.
+///
+/// Serialised as `{ code, message, details }` where `code` is stable and safe
+/// for the frontend to match on; `message` is for display only.
+#[derive(Debug, Error)]
+pub enum CyDogError {
+    /// The LLM backend is not running or the URL is wrong
+    #[error("Could not reach {backend} at {url}. Is it running?")]
+    ProviderUnreachable { backend: String, url: String },
+
+    /// The requested chat or embedding model is not installed
+    #[error("Model {model} is not available on the model server")]
+    ModelMissing { model: String },
+
+    /// A query arrived before anything was ingested
+    #[error("No data has been indexed yet. Please ingest a folder first.")]
+    NoIndex,
+
+    /// No collection has the requested id
+    #[error("Collection not found: {id}")]
+    CollectionNotFound { id: String },
+
+    /// The index holds no rows from the given file
+    #[error("No indexed rows come from {file}")]
+    SourceNotFound { file: String },
+
+    /// The data folder does not exist or is not a directory
+    #[error("Folder not found: {path}")]
+    FolderNotFound { path: String },
+
+    /// A data file could not be parsed (row is 1-indexed when known)
+    #[error("Failed to parse {file}{}: {message}", row_suffix(.row))]
+    FileParse {
+        file: String,
+        row: Option<usize>,
+        message: String,
+    },
+
+    /// Reading or writing a file failed
+    #[error("Failed to access {path}: {message}")]
+    Io { path: String, message: String },
+
+    /// Embedding the documents or the query failed
+    #[error("Failed to build embeddings with {model}: {message}")]
+    EmbeddingFailed { model: String, message: String },
+
+    /// The chat model failed to produce an answer
+    #[error("Failed to generate response: {message}")]
+    GenerationFailed { message: String },
+
+    /// The saved index cannot be read back
+    #[error("Saved index at {path} is unreadable: {message}")]
+    IndexCorrupt { path: String, message: String },
+
+    /// The saved index was built with a different embedding model
+    #[error("Index was built with embedding model {indexed} but {configured} is configured")]
+    EmbeddingModelMismatch { indexed: String, configured: String },
+
+    /// The model server answered with something we did not understand
+    #[error("{url} returned an invalid response: {message}")]
+    InvalidResponse { url: String, message: String },
+
+    /// The user cancelled the operation
+    #[error("Cancelled")]
+    Cancelled,
+
+    /// A command argument was rejected
+    #[error("{message}")]
+    InvalidInput { message: String },
+
+    /// The local HTTP API could not be started
+    #[error("Failed to start the local API server: {message}")]
+    ServerFailed { message: String },
+
+    /// The folder watcher could not be started
+    #[error("Failed to watch the data folder: {message}")]
+    WatchFailed { message: String },
+}
+
+impl CyDogError {
+    /// Stable identifier the frontend can match on
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ProviderUnreachable { .. } => "PROVIDER_UNREACHABLE",
+            Self::ModelMissing { .. } => "MODEL_MISSING",
+            Self::NoIndex => "NO_INDEX",
+            Self::CollectionNotFound { .. } => "COLLECTION_NOT_FOUND",
+            Self::SourceNotFound { .. } => "SOURCE_NOT_FOUND",
+            Self::FolderNotFound { .. } => "FOLDER_NOT_FOUND",
+            Self::FileParse { .. } => "FILE_PARSE",
+            Self::Io { .. } => "IO",
+            Self::EmbeddingFailed { .. } => "EMBEDDING_FAILED",
+            Self::GenerationFailed { .. } => "GENERATION_FAILED",
+            Self::IndexCorrupt { .. } => "INDEX_CORRUPT",
+            Self::EmbeddingModelMismatch { .. } => "EMBEDDING_MODEL_MISMATCH",
+            Self::InvalidResponse { .. } => "INVALID_RESPONSE",
+            Self::Cancelled => "CANCELLED",
+            Self::InvalidInput { .. } => "INVALID_INPUT",
+            Self::ServerFailed { .. } => "SERVER_FAILED",
+            Self::WatchFailed { .. } => "WATCH_FAILED",
+        }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::InvalidInput {
+            message: message.into(),
+        }
+    }
+
+    /// Structured fields for the frontend (file and row, model name, ...)
+    fn details(&self) -> Value {
+        match self {
+            Self::ProviderUnreachable { backend, url } => json!({ "backend": backend, "url": url }),
+            Self::InvalidResponse { url, .. } => json!({ "url": url }),
+            Self::ModelMissing { model } | Self::EmbeddingFailed { model, .. } => {
+                json!({ "model": model })
+            }
+            Self::FolderNotFound { path }
+            | Self::Io { path, .. }
+            | Self::IndexCorrupt { path, .. } => json!({ "path": path }),
+            Self::CollectionNotFound { id } => json!({ "id": id }),
+            Self::SourceNotFound { file } => json!({ "file": file }),
+            Self::FileParse { file, row, .. } => json!({ "file": file, "row": row }),
+            Self::EmbeddingModelMismatch {
+                indexed,
+                configured,
+            } => json!({ "indexed": indexed, "configured": configured }),
+            Self::NoIndex
+            | Self::GenerationFailed { .. }
+            | Self::Cancelled
+            | Self::InvalidInput { .. }
+            | Self::ServerFailed { .. }
+            | Self::WatchFailed { .. } => Value::Null,
+        }
+    }
+
+    /// Map a core error, given the settings of the provider that produced it
+    pub fn from_core(error: cy_dog_core::Error, provider: &ProviderConfig) -> Self {
+        use cy_dog_core::Error;
+
+        let unreachable = |url: String| Self::ProviderUnreachable {
+            backend: provider.kind.label().to_string(),
+            url,
+        };
+        if error.is_unreachable() {
+            return unreachable(provider.base_url.clone());
+        }
+
+        if error.is_model_missing() {
+            if let Error::Embedding { model, .. } | Error::Completion { model, .. } = &error {
+                return Self::ModelMissing {
+                    model: model.clone(),
+                };
+            }
+        }
+
+        let message = error.to_string();
+        match error {
+            Error::DirectoryNotFound(path) | Error::NotADirectory(path) => {
+                Self::FolderNotFound { path }
+            }
+            Error::Io { path, source } => Self::Io {
+                path,
+                message: source.to_string(),
+            },
+            Error::Parse { file, row, message } => Self::FileParse { file, row, message },
+            Error::NoDocuments => Self::invalid_input(message),
+            Error::Embedding { model, source } => Self::EmbeddingFailed {
+                model,
+                message: source.to_string(),
+            },
+            Error::CorruptIndex { path, message } => Self::IndexCorrupt { path, message },
+            Error::EmbeddingModelMismatch {
+                indexed,
+                configured,
+            } => Self::EmbeddingModelMismatch {
+                indexed,
+                configured,
+            },
+            Error::Completion { source, .. } => Self::GenerationFailed {
+                message: source.to_string(),
+            },
+            Error::Connection { url, .. } => unreachable(url),
+            Error::InvalidResponse { url, message } => Self::InvalidResponse { url, message },
+            Error::SourceNotFound(file) => Self::SourceNotFound { file },
+            Error::CollectionNotFound(id) => Self::CollectionNotFound { id },
+            Error::InvalidCollection(message) | Error::InvalidSetting(message) => {
+                Self::InvalidInput { message }
+            }
+            Error::DuplicateSourceName { .. } => Self::invalid_input(message),
+        }
+    }
+}
+
+impl Serialize for CyDogError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("CyDogError", 3)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.serialize_field("details", &self.details())?;
+        error.end()
+    }
+}
+
+fn row_suffix(row: &Option<usize>) -> String {
+    row.map(|r| format!(" at row {}", r)).unwrap_or_default()
+}
+
+pub type Result<T> = std::result::Result<T, CyDogError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialises_code_message_and_details() {
+        let error = CyDogError::FileParse {
+            file: "a.csv".to_string(),
+            row: Some(3),
+            message: "bad quote".to_string(),
+        };
+
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["code"], "FILE_PARSE");
+        assert_eq!(
+            value["message"],
+            "Failed to parse a.csv at row 3: bad quote"
+        );
+        assert_eq!(value["details"]["row"], 3);
+    }
+}
@@ -0,0 +1,18 @@
+//! Everything the desktop app does besides talking to the webview: the shared
+//! application state, the local HTTP API and the folder watcher.
+//!
+//! Kept free of Tauri so the end-to-end tests build and run without the
+//! system libraries the webview needs.
+
+pub mod error;
+pub mod server;
+pub mod state;
+pub mod watcher;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::{CyDogError, Result};
+pub use server::{ApiServer, ApiServerStatus, DEFAULT_API_PORT};
+pub use state::AppState;
+pub use watcher::{ChangeListener, FolderWatcher, WatchStatus};
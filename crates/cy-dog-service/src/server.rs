@@ -0,0 +1,374 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
+    Json, Router,
+};
+use futures::stream::{self, BoxStream, StreamExt};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fs;
+use std::io::Write;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{oneshot, Mutex};
+
+use cy_dog_core::{
+    default_data_dir, stream_rag_response, EmbeddableDocument, IngestResult, QueryResult,
+    TOP_K_RESULTS,
+};
+
+use crate::error::CyDogError;
+use crate::state::{AppState, AppStatus};
+
+/// Port used when the frontend does not ask for a specific one
+pub const DEFAULT_API_PORT: u16 = 8765;
+
+/// File holding the bearer token, created on first start
+const TOKEN_FILE_NAME: &str = "api_token";
+
+/// Handle to the optional loopback HTTP API
+#[derive(Default)]
+pub struct ApiServer {
+    running: Mutex<Option<RunningServer>>,
+}
+
+struct RunningServer {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+    /// Why the server stopped on its own, if it did
+    failure: Arc<std::sync::Mutex<Option<String>>>,
+}
+
+impl RunningServer {
+    fn failure(&self) -> Option<String> {
+        self.failure
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+/// Server status returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiServerStatus {
+    pub running: bool,
+    pub url: Option<String>,
+    /// Bearer token clients must send in the Authorization header
+    pub token: String,
+    /// Why the server stopped, if it failed after starting
+    pub error: Option<String>,
+}
+
+impl ApiServer {
+    /// Start listening on 127.0.0.1 (no-op if already running; a server that
+    /// failed is started again)
+    pub async fn start(
+        &self,
+        app: Arc<AppState>,
+        port: u16,
+    ) -> Result<ApiServerStatus, CyDogError> {
+        let token = load_or_create_token()?;
+        let mut running = self.running.lock().await;
+
+        if running.as_ref().is_none_or(|s| s.failure().is_some()) {
+            let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+                .await
+                .map_err(|e| CyDogError::ServerFailed {
+                    message: format!("cannot bind 127.0.0.1:{}: {}", port, e),
+                })?;
+            let port = listener
+                .local_addr()
+                .map_err(|e| CyDogError::ServerFailed {
+                    message: e.to_string(),
+                })?
+                .port();
+
+            let router = router(ServerState {
+                app,
+                token: Arc::new(token.clone()),
+            });
+            let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+            let failure = Arc::new(std::sync::Mutex::new(None));
+
+            let failed = failure.clone();
+            tokio::spawn(async move {
+                let server = axum::serve(listener, router).with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                });
+                if let Err(e) = server.await {
+                    *failed.lock().unwrap_or_else(|e| e.into_inner()) = Some(e.to_string());
+                }
+            });
+
+            *running = Some(RunningServer {
+                port,
+                shutdown,
+                failure,
+            });
+        }
+
+        Ok(status_of(running.as_ref(), token))
+    }
+
+    /// Stop the server if it is running
+    pub async fn stop(&self) {
+        if let Some(server) = self.running.lock().await.take() {
+            let _ = server.shutdown.send(());
+        }
+    }
+
+    pub async fn status(&self) -> Result<ApiServerStatus, CyDogError> {
+        let token = load_or_create_token()?;
+        let running = self.running.lock().await;
+        Ok(status_of(running.as_ref(), token))
+    }
+}
+
+fn status_of(running: Option<&RunningServer>, token: String) -> ApiServerStatus {
+    let error = running.and_then(RunningServer::failure);
+    let serving = running.filter(|_| error.is_none());
+    ApiServerStatus {
+        running: serving.is_some(),
+        url: serving.map(|s| format!("http://127.0.0.1:{}", s.port)),
+        token,
+        error,
+    }
+}
+
+/// Read the API token, generating and saving one on first run
+fn load_or_create_token() -> Result<String, CyDogError> {
+    let path: PathBuf = default_data_dir().join(TOKEN_FILE_NAME);
+
+    if let Ok(token) = fs::read_to_string(&path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            return Ok(token.to_string());
+        }
+    }
+
+    let token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+
+    let io_error = |path: &std::path::Path, e: std::io::Error| CyDogError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+
+    // Keep the token private to the current user from the moment it exists;
+    // an empty token file left behind is replaced so the mode applies
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| io_error(&path, e))?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&path)
+        .and_then(|mut file| file.write_all(token.as_bytes()))
+        .map_err(|e| io_error(&path, e))?;
+
+    Ok(token)
+}
+
+#[derive(Clone)]
+struct ServerState {
+    app: Arc<AppState>,
+    token: Arc<String>,
+}
+
+fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/api/status", get(status))
+        .route("/api/ingest", post(ingest))
+        .route("/api/search", post(search))
+        .route("/api/ask", post(ask))
+        .route("/api/ask/stream", post(ask_stream))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+/// Reject requests without the right `Authorization: Bearer <token>` header
+async fn require_token(
+    State(server): State<ServerState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), server.token.as_bytes()));
+
+    if !authorized {
+        let body = serde_json::json!({
+            "code": "UNAUTHORIZED",
+            "message": "Missing or invalid bearer token",
+            "details": null,
+        });
+        return (StatusCode::UNAUTHORIZED, Json(body)).into_response();
+    }
+
+    next.run(request).await
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// JSON error body: the serialised `CyDogError` (`{"code", "message", "details"}`)
+struct ApiError(CyDogError);
+
+impl From<CyDogError> for ApiError {
+    fn from(error: CyDogError) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            CyDogError::NoIndex => StatusCode::CONFLICT,
+            CyDogError::FolderNotFound { .. }
+            | CyDogError::ModelMissing { .. }
+            | CyDogError::CollectionNotFound { .. }
+            | CyDogError::SourceNotFound { .. } => StatusCode::NOT_FOUND,
+            CyDogError::FileParse { .. } | CyDogError::InvalidInput { .. } => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            CyDogError::ProviderUnreachable { .. } | CyDogError::InvalidResponse { .. } => {
+                StatusCode::BAD_GATEWAY
+            }
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(self.0)).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct IngestRequest {
+    folder_path: String,
+}
+
+#[derive(Deserialize)]
+struct QueryRequest {
+    query: String,
+    #[serde(default)]
+    top_k: Option<usize>,
+    /// Collection ids to search (the active collection if empty)
+    #[serde(default)]
+    collections: Vec<String>,
+}
+
+async fn status(State(server): State<ServerState>) -> Json<AppStatus> {
+    Json(server.app.status().await)
+}
+
+async fn ingest(
+    State(server): State<ServerState>,
+    Json(request): Json<IngestRequest>,
+) -> Result<Json<IngestResult>, ApiError> {
+    server
+        .app
+        .ingest(request.folder_path)
+        .await
+        .map(Json)
+        .map_err(ApiError)
+}
+
+async fn search(
+    State(server): State<ServerState>,
+    Json(request): Json<QueryRequest>,
+) -> Result<Json<Vec<EmbeddableDocument>>, ApiError> {
+    let top_k = request.top_k.unwrap_or(TOP_K_RESULTS);
+    server
+        .app
+        .search(&request.query, top_k, &request.collections)
+        .await
+        .map(Json)
+        .map_err(ApiError)
+}
+
+async fn ask(
+    State(server): State<ServerState>,
+    Json(request): Json<QueryRequest>,
+) -> Result<Json<QueryResult>, ApiError> {
+    let top_k = request.top_k.unwrap_or(TOP_K_RESULTS);
+    server
+        .app
+        .ask(&request.query, top_k, &request.collections)
+        .await
+        .map(Json)
+        .map_err(ApiError)
+}
+
+/// Stream an answer as Server-Sent Events: one `sources` event, then `token`
+/// events as the model writes, then `done` (or `error`)
+async fn ask_stream(
+    State(server): State<ServerState>,
+    Json(request): Json<QueryRequest>,
+) -> Result<Sse<BoxStream<'static, Result<Event, Infallible>>>, ApiError> {
+    let top_k = request.top_k.unwrap_or(TOP_K_RESULTS);
+    let docs = server
+        .app
+        .search(&request.query, top_k, &request.collections)
+        .await?;
+
+    let (tokens, sources): (BoxStream<'static, Result<String, CyDogError>>, Vec<String>) = if docs
+        .is_empty()
+    {
+        let message = "No relevant information found in the indexed data.".to_string();
+        (stream::once(async move { Ok(message) }).boxed(), Vec::new())
+    } else {
+        let model_name = server.app.selected_model.read().await.clone();
+        let provider = server.app.provider().await;
+        let settings = provider.config().clone();
+        let (tokens, sources) = stream_rag_response(&request.query, docs, &provider, &model_name)
+            .await
+            .map_err(|e| CyDogError::from_core(e, &settings))?;
+        (
+            tokens
+                .map(move |t| t.map_err(|e| CyDogError::from_core(e, &settings)))
+                .boxed(),
+            sources,
+        )
+    };
+
+    let events = stream::once(async move { json_event("sources", &sources) })
+        .chain(tokens.map(|token| match token {
+            Ok(text) => json_event("token", &serde_json::json!({ "text": text })),
+            Err(error) => json_event("error", &error),
+        }))
+        .chain(stream::once(async {
+            Event::default().event("done").data("")
+        }))
+        .map(Ok)
+        .boxed();
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+fn json_event<T: Serialize>(name: &str, data: &T) -> Event {
+    Event::default()
+        .event(name)
+        .json_data(data)
+        .unwrap_or_default()
+}
@@ -0,0 +1,813 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify, RwLock};
+
+use cy_dog_core::{
+    add_sources, answer_across_collections, answer_question, ingest_folder, remove_source,
+    search_collections, source_name, ChatModel, CollectionInfo, CollectionStore, ColumnRole,
+    DatasetSchema, EmbeddableDocument, IngestResult, NumberFormat, Provider, ProviderConfig,
+    ProviderStatus, QueryResult, VectorIndex, DEFAULT_CHAT_MODEL, DEFAULT_COLLECTION_ID,
+};
+
+use crate::error::{CyDogError, Result};
+use crate::server::ApiServer;
+use crate::watcher::{FolderWatcher, WatchTarget};
+
+/// An ingested index together with the metadata describing it.
+///
+/// Snapshots are immutable: ingesting builds a new one and swaps it in, so a
+/// query keeps using the snapshot it started with.
+pub struct IndexSnapshot {
+    pub index: VectorIndex,
+    /// Path to the ingested data folder
+    pub data_folder: Option<String>,
+    /// Number of documents ingested
+    pub document_count: usize,
+}
+
+impl IndexSnapshot {
+    fn new(index: VectorIndex, data_folder: Option<String>) -> Self {
+        let document_count = index.len();
+        Self {
+            index,
+            data_folder,
+            document_count,
+        }
+    }
+}
+
+/// Application state shared across Tauri commands
+pub struct AppState {
+    /// Index snapshots of the collections read so far, by collection id; the
+    /// lock is only held long enough to clone or replace an `Arc`
+    indexes: RwLock<HashMap<String, Arc<IndexSnapshot>>>,
+    /// The collection commands act on unless told otherwise
+    active_collection: RwLock<CollectionInfo>,
+    /// Held while an index is rebuilt so concurrent changes cannot overwrite each other
+    updating: Mutex<()>,
+    /// Currently selected chat model name
+    pub selected_model: RwLock<String>,
+    /// LLM backend used for chat, embeddings and model listing
+    pub provider: RwLock<ProviderConfig>,
+    /// Named collections and where their indexes are persisted (shared with the CLI)
+    pub collections: CollectionStore,
+    /// The optional local HTTP API (stopped by default)
+    pub api_server: ApiServer,
+    /// Optional watcher re-indexing the active collection's files (stopped by default)
+    pub watcher: FolderWatcher,
+    /// Wakes in-flight questions so they can stop early
+    pub cancel: Notify,
+    /// Saved settings that could not be read at startup, replaced by defaults
+    load_warnings: Vec<String>,
+}
+
+impl AppState {
+    /// State using the provider settings saved by the last session
+    pub fn new() -> Self {
+        let collections = CollectionStore::default_location();
+        let (provider, warning) = match ProviderConfig::load(collections.dir()) {
+            Ok(provider) => (provider, None),
+            Err(e) => (
+                ProviderConfig::default(),
+                Some(format!("Could not read provider settings: {}", e)),
+            ),
+        };
+        let mut state = Self::with_collections(provider, collections);
+        state.load_warnings.extend(warning);
+        state
+    }
+    
+    /// State using the given provider settings and collections; the active
+    /// collection's index is read on first use
+    pub fn with_collections(mut provider: ProviderConfig, collections: CollectionStore) -> Self {
+        let mut load_warnings = Vec::new();
+        let active = collections.active().unwrap_or_else(|e| {
+            load_warnings.push(format!("Could not read collections: {}", e));
+            CollectionInfo::new(DEFAULT_COLLECTION_ID, "Default", &provider.embedding_model)
+        });
+        
+        // Queries must be embedded with the model the collection's index was
+        // built with; until it has one, the saved setting applies
+        if collections.index_store(&active.id).path().exists() {
+            provider.embedding_model = active.embedding_model.clone();
+        }
+        let selected_model = active
+            .chat_model
+            .clone()
+            .unwrap_or_else(|| DEFAULT_CHAT_MODEL.to_string());
+        
+        Self {
+            indexes: RwLock::new(HashMap::new()),
+            active_collection: RwLock::new(active),
+            updating: Mutex::new(()),
+            selected_model: RwLock::new(selected_model),
+            provider: RwLock::new(provider),
+            collections,
+            api_server: ApiServer::default(),
+            watcher: FolderWatcher::default(),
+            cancel: Notify::new(),
+            load_warnings,
+        }
+    }
+    
+    /// The active collection's index snapshot, without holding any lock afterwards
+    pub async fn snapshot(&self) -> Option<Arc<IndexSnapshot>> {
+        self.require_snapshot().await.ok()
+    }
+    
+    /// The active collection's snapshot, or `NoIndex` if nothing was ingested yet
+    pub async fn require_snapshot(&self) -> Result<Arc<IndexSnapshot>> {
+        let active = self.active_collection.read().await.clone();
+        self.collection_snapshot(&active)
+            .await?
+            .ok_or(CyDogError::NoIndex)
+    }
+    
+    /// A collection's snapshot, reading its saved index on first use
+    async fn collection_snapshot(
+        &self,
+        collection: &CollectionInfo,
+    ) -> Result<Option<Arc<IndexSnapshot>>> {
+        if let Some(snapshot) = self.indexes.read().await.get(&collection.id) {
+            return Ok(Some(snapshot.clone()));
+        }
+        
+        let provider = ProviderConfig {
+            embedding_model: collection.embedding_model.clone(),
+            ..self.provider.read().await.clone()
+        };
+        let loaded = self
+            .collections
+            .index_store(&collection.id)
+            .load(&Provider::new(provider.clone()))
+            .map_err(|e| CyDogError::from_core(e, &provider))?;
+        
+        let Some(loaded) = loaded else {
+            return Ok(None);
+        };
+        
+        // An ingest that finished meanwhile wins over the copy read from disk
+        let snapshot = Arc::new(IndexSnapshot::new(loaded.index, loaded.data_folder));
+        let mut indexes = self.indexes.write().await;
+        Ok(Some(indexes.entry(collection.id.clone()).or_insert(snapshot).clone()))
+    }
+    
+    /// A provider client for the current settings
+    pub async fn provider(&self) -> Provider {
+        Provider::new(self.provider.read().await.clone())
+    }
+    
+    /// Ingest a folder into the active collection, persist the index and make it current
+    pub async fn ingest(&self, folder_path: String) -> Result<IngestResult> {
+        let _updating = self.updating.lock().await;
+        
+        // A switch while ingesting does not redirect the result to another collection
+        let collection = self.active_collection.read().await.clone();
+        let provider = self.provider().await;
+        let settings = provider.config().clone();
+        
+        let mut schema = collection.schema.clone();
+        let ingestion = ingest_folder(&folder_path, &provider, &mut schema)
+            .await
+            .map_err(|e| CyDogError::from_core(e, &settings))?;
+        
+        let index = match ingestion.index {
+            Some(index) => index,
+            None => return Ok(ingestion.result),
+        };
+        
+        let folder = folder_path.clone();
+        self.replace_index(&collection.id, index, Some(folder_path), &settings, |info| {
+            info.folders = vec![folder];
+            info.files.clear();
+            info.schema = schema;
+        })
+        .await?;
+        
+        Ok(ingestion.result)
+    }
+    
+    /// Append files or folders to the active collection's index (files already
+    /// indexed are re-read); existing rows keep their ids
+    pub async fn add_sources(&self, paths: Vec<String>) -> Result<IngestResult> {
+        let _updating = self.updating.lock().await;
+        
+        let collection = self.active_collection.read().await.clone();
+        self.add_to_collection(&collection, paths).await
+    }
+    
+    /// Append files or folders to a collection's index; the caller holds `updating`
+    async fn add_to_collection(
+        &self,
+        collection: &CollectionInfo,
+        paths: Vec<String>,
+    ) -> Result<IngestResult> {
+        let existing = self.collection_snapshot(collection).await?;
+        
+        // New rows are embedded with the model the index was built with
+        let mut config = self.provider.read().await.clone();
+        if let Some(snapshot) = &existing {
+            config.embedding_model = snapshot.index.embedding_model_name().to_string();
+        }
+        let provider = Provider::new(config);
+        let settings = provider.config().clone();
+        
+        let mut schema = collection.schema.clone();
+        let tracked: Vec<String> = collection
+            .folders
+            .iter()
+            .chain(&collection.files)
+            .cloned()
+            .collect();
+        let ingestion = add_sources(
+            existing.as_ref().map(|s| &s.index),
+            &paths,
+            &tracked,
+            &provider,
+            &mut schema,
+        )
+        .await
+        .map_err(|e| CyDogError::from_core(e, &settings))?;
+        
+        let index = match ingestion.index {
+            Some(index) => index,
+            None => return Ok(ingestion.result),
+        };
+        
+        let data_folder = existing
+            .and_then(|s| s.data_folder.clone())
+            .or_else(|| paths.iter().find(|p| Path::new(p).is_dir()).cloned());
+        self.replace_index(&collection.id, index, data_folder, &settings, |info| {
+            info.schema = schema;
+            for path in paths {
+                let in_folder = Path::new(&path)
+                    .parent()
+                    .is_some_and(|dir| info.folders.iter().any(|f| Path::new(f) == dir));
+                let sources = if Path::new(&path).is_dir() {
+                    &mut info.folders
+                } else if in_folder {
+                    // Already covered by a tracked folder
+                    continue;
+                } else {
+                    &mut info.files
+                };
+                if !sources.contains(&path) {
+                    sources.push(path);
+                }
+            }
+        })
+        .await?;
+        
+        Ok(ingestion.result)
+    }
+    
+    /// Re-read every folder and file of the active collection, e.g. to apply
+    /// new column roles; row ids stay the same
+    pub async fn reindex_collection(&self) -> Result<IngestResult> {
+        let collection = self.active_collection.read().await.clone();
+        let sources: Vec<String> = collection
+            .folders
+            .into_iter()
+            .chain(collection.files)
+            .collect();
+        
+        if sources.is_empty() {
+            return Err(CyDogError::invalid_input(
+                "Nothing has been ingested into this collection yet",
+            ));
+        }
+        self.add_sources(sources).await
+    }
+    
+    /// Drop every row of one source file from the active collection's index
+    pub async fn remove_source(&self, source_file: &str) -> Result<SourceRemoval> {
+        let _updating = self.updating.lock().await;
+        
+        let collection = self.active_collection.read().await.clone();
+        self.remove_from_collection(&collection, source_file).await
+    }
+    
+    /// Drop every row of one source file from a collection's index; the caller
+    /// holds `updating`
+    async fn remove_from_collection(
+        &self,
+        collection: &CollectionInfo,
+        source_file: &str,
+    ) -> Result<SourceRemoval> {
+        let snapshot = self
+            .collection_snapshot(collection)
+            .await?
+            .ok_or(CyDogError::NoIndex)?;
+        
+        let provider = Provider::new(ProviderConfig {
+            embedding_model: snapshot.index.embedding_model_name().to_string(),
+            ..self.provider.read().await.clone()
+        });
+        let settings = provider.config().clone();
+        
+        let (index, documents_removed) = remove_source(&snapshot.index, source_file, &provider)
+            .map_err(|e| CyDogError::from_core(e, &settings))?;
+        let document_count = index.len();
+        
+        let data_folder = snapshot.data_folder.clone();
+        self.replace_index(&collection.id, index, data_folder, &settings, |info| {
+            info.files
+                .retain(|file| source_name(Path::new(file)) != source_file);
+        })
+        .await?;
+        
+        Ok(SourceRemoval {
+            source_file: source_file.to_string(),
+            documents_removed,
+            document_count,
+        })
+    }
+    
+    /// Re-index files of a collection (active or not) that were created,
+    /// modified or deleted since they were indexed; used by the folder watcher
+    pub async fn reindex_changed(
+        &self,
+        collection_id: &str,
+        paths: Vec<PathBuf>,
+    ) -> Result<IndexChange> {
+        let _updating = self.updating.lock().await;
+        
+        let collection = self.collections.get(collection_id).map_err(collection_error)?;
+        let count = |snapshot: Option<Arc<IndexSnapshot>>| snapshot.map_or(0, |s| s.document_count);
+        let before = count(self.collection_snapshot(&collection).await?);
+        
+        let (present, deleted): (Vec<PathBuf>, Vec<PathBuf>) =
+            paths.into_iter().partition(|path| path.is_file());
+        let files = present.iter().chain(&deleted).map(|path| source_name(path)).collect();
+        
+        if !present.is_empty() {
+            let present = present
+                .iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect();
+            self.add_to_collection(&collection, present).await?;
+        }
+        for path in &deleted {
+            // Files that were never indexed (or an index emptied meanwhile) need no change
+            match self.remove_from_collection(&collection, &source_name(path)).await {
+                Ok(_) | Err(CyDogError::SourceNotFound { .. }) | Err(CyDogError::NoIndex) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        
+        let document_count = count(self.collection_snapshot(&collection).await?);
+        Ok(IndexChange {
+            collection_id: collection.id,
+            files,
+            rows_added: document_count.saturating_sub(before),
+            rows_removed: before.saturating_sub(document_count),
+            document_count,
+        })
+    }
+    
+    /// Folders and files of the active collection, as the watcher should follow them
+    pub async fn watch_target(&self) -> WatchTarget {
+        watch_target(&*self.active_collection.read().await)
+    }
+    
+    /// Persist a collection's new index, record how it was built and swap it
+    /// in; an empty index is removed as if the collection was never ingested
+    async fn replace_index(
+        &self,
+        collection_id: &str,
+        index: VectorIndex,
+        data_folder: Option<String>,
+        settings: &ProviderConfig,
+        record: impl FnOnce(&mut CollectionInfo),
+    ) -> Result<()> {
+        if index.is_empty() {
+            self.collections
+                .index_store(collection_id)
+                .clear()
+                .map_err(collection_error)?;
+            let updated = self
+                .collections
+                .modify(collection_id, |info| {
+                    info.document_count = 0;
+                    record(info);
+                })
+                .map_err(collection_error)?;
+            self.refresh_active(&updated).await;
+            self.indexes.write().await.remove(collection_id);
+            return Ok(());
+        }
+        
+        // Persist so the index survives restarts and is visible to the CLI
+        self.collections
+            .index_store(collection_id)
+            .save(&index, data_folder.as_deref())
+            .map_err(|e| CyDogError::from_core(e, settings))?;
+        
+        // Remember how the collection was built
+        let embedding_model = index.embedding_model_name().to_string();
+        let document_count = index.len();
+        let updated = self
+            .collections
+            .modify(collection_id, |info| {
+                info.embedding_model = embedding_model;
+                info.document_count = document_count;
+                record(info);
+            })
+            .map_err(collection_error)?;
+        self.refresh_active(&updated).await;
+        
+        // Swap the index and its metadata in one step
+        let snapshot = IndexSnapshot::new(index, data_folder);
+        self.indexes
+            .write()
+            .await
+            .insert(collection_id.to_string(), Arc::new(snapshot));
+        
+        Ok(())
+    }
+    
+    /// Chat models offered by the provider
+    pub async fn list_models(&self) -> Result<Vec<ChatModel>> {
+        let provider = self.provider().await;
+        
+        provider
+            .list_chat_models()
+            .await
+            .map_err(|e| CyDogError::from_core(e, provider.config()))
+    }
+    
+    /// Whether the provider is reachable and has the embedding model
+    pub async fn provider_status(&self) -> ProviderStatus {
+        self.provider().await.status().await
+    }
+    
+    /// Snapshot of what is indexed and which model is selected
+    pub async fn status(&self) -> AppStatus {
+        let collection = self.active_collection.read().await.clone();
+        let snapshot = self.snapshot().await;
+        
+        AppStatus {
+            is_indexed: snapshot.is_some(),
+            document_count: snapshot.as_ref().map_or(0, |s| s.document_count),
+            sources: snapshot.as_ref().map_or_else(Vec::new, |s| {
+                s.index
+                    .sources()
+                    .into_iter()
+                    .map(|(source_file, rows)| IndexedSource { source_file, rows })
+                    .collect()
+            }),
+            data_folder: snapshot.and_then(|s| s.data_folder.clone()),
+            selected_model: self.selected_model.read().await.clone(),
+            collection_id: collection.id,
+            collection_name: collection.name,
+            warnings: self.load_warnings.clone(),
+        }
+    }
+    
+    /// Snapshots to query: the given collections, or the active one if none are given
+    async fn query_snapshots(
+        &self,
+        collection_ids: &[String],
+    ) -> Result<Vec<(String, Arc<IndexSnapshot>)>> {
+        if collection_ids.is_empty() {
+            let name = self.active_collection.read().await.name.clone();
+            return Ok(vec![(name, self.require_snapshot().await?)]);
+        }
+        
+        let mut snapshots = Vec::with_capacity(collection_ids.len());
+        for id in collection_ids {
+            let collection = self.collections.get(id).map_err(collection_error)?;
+            let snapshot = self
+                .collection_snapshot(&collection)
+                .await?
+                .ok_or(CyDogError::NoIndex)?;
+            snapshots.push((collection.name, snapshot));
+        }
+        Ok(snapshots)
+    }
+    
+    /// Retrieve the rows most similar to a query from the given collections
+    /// (the active one if empty)
+    pub async fn search(
+        &self,
+        query: &str,
+        top_k: usize,
+        collection_ids: &[String],
+    ) -> Result<Vec<EmbeddableDocument>> {
+        let snapshots = self.query_snapshots(collection_ids).await?;
+        let settings = self.provider.read().await.clone();
+        
+        let result = match snapshots.as_slice() {
+            [(_, snapshot)] => snapshot.index.search(query, top_k).await,
+            _ => search_collections(&named_indexes(&snapshots), query, top_k).await,
+        };
+        result.map_err(|e| CyDogError::from_core(e, &settings))
+    }
+    
+    /// Answer a question with the selected chat model from the given collections
+    /// (the active one if empty)
+    pub async fn ask(
+        &self,
+        query: &str,
+        top_k: usize,
+        collection_ids: &[String],
+    ) -> Result<QueryResult> {
+        // Take the current snapshots; a concurrent ingest swaps in new ones
+        // without waiting for this answer
+        let snapshots = self.query_snapshots(collection_ids).await?;
+        let indexes = named_indexes(&snapshots);
+        
+        // Get the selected model and provider
+        let model_name = self.selected_model.read().await.clone();
+        let provider = self.provider().await;
+        
+        let answer = async {
+            match indexes.as_slice() {
+                [(_, index)] => answer_question(index, &provider, &model_name, query, top_k).await,
+                _ => {
+                    answer_across_collections(&indexes, &provider, &model_name, query, top_k)
+                        .await
+                }
+            }
+        };
+        
+        tokio::select! {
+            result = answer => {
+                result.map_err(|e| CyDogError::from_core(e, provider.config()))
+            }
+            _ = self.cancel.notified() => Err(CyDogError::Cancelled),
+        }
+    }
+    
+    /// Stop every question that is currently being answered
+    pub fn cancel_questions(&self) {
+        self.cancel.notify_waiters();
+    }
+    
+    /// Select the chat model and remember it for the active collection
+    pub async fn set_chat_model(&self, model_name: String) -> Result<()> {
+        let id = self.active_collection.read().await.id.clone();
+        let updated = self
+            .collections
+            .modify(&id, |info| info.chat_model = Some(model_name.clone()))
+            .map_err(collection_error)?;
+        self.refresh_active(&updated).await;
+        
+        *self.selected_model.write().await = model_name;
+        Ok(())
+    }
+    
+    /// Switch the provider and remember it for the next launch
+    pub async fn set_provider(&self, settings: ProviderConfig) -> Result<()> {
+        let _updating = self.updating.lock().await;
+        
+        settings
+            .save(self.collections.dir())
+            .map_err(|e| CyDogError::from_core(e, &settings))?;
+        let previous = std::mem::replace(&mut *self.provider.write().await, settings.clone());
+        if previous == settings {
+            return Ok(());
+        }
+        
+        // The active collection is (re)built with the new embedding model
+        if previous.embedding_model != settings.embedding_model {
+            let id = self.active_collection.read().await.id.clone();
+            let updated = self
+                .collections
+                .modify(&id, |info| info.embedding_model = settings.embedding_model)
+                .map_err(collection_error)?;
+            self.refresh_active(&updated).await;
+        }
+        
+        // Loaded indexes embed queries with a client for the old settings;
+        // they are read again on next use
+        self.indexes.write().await.clear();
+        Ok(())
+    }
+    
+    /// Column roles of the active collection
+    pub async fn column_schema(&self) -> DatasetSchema {
+        self.active_collection.read().await.schema.clone()
+    }
+    
+    /// Change how a column is used, for the whole collection or only `file`;
+    /// takes effect the next time the data is indexed
+    pub async fn set_column_role(
+        &self,
+        column: &str,
+        role: ColumnRole,
+        file: Option<&str>,
+    ) -> Result<DatasetSchema> {
+        let id = self.active_collection.read().await.id.clone();
+        let updated = self
+            .collections
+            .modify(&id, |info| info.schema.set_role(file, column, role))
+            .map_err(collection_error)?;
+        self.refresh_active(&updated).await;
+        Ok(updated.schema)
+    }
+    
+    /// Include or exclude one sheet of a workbook; takes effect the next time
+    /// the data is indexed
+    pub async fn set_sheet_included(
+        &self,
+        file: &str,
+        sheet: &str,
+        included: bool,
+    ) -> Result<DatasetSchema> {
+        let id = self.active_collection.read().await.id.clone();
+        let updated = self
+            .collections
+            .modify(&id, |info| info.schema.set_sheet_included(file, sheet, included))
+            .map_err(collection_error)?;
+        self.refresh_active(&updated).await;
+        Ok(updated.schema)
+    }
+    
+    /// Set how a workbook column's numbers are written; takes effect the next
+    /// time the data is indexed
+    pub async fn set_number_format(
+        &self,
+        column: &str,
+        format: NumberFormat,
+    ) -> Result<DatasetSchema> {
+        let id = self.active_collection.read().await.id.clone();
+        let updated = self
+            .collections
+            .modify(&id, |info| info.schema.set_number_format(column, format))
+            .map_err(collection_error)?;
+        self.refresh_active(&updated).await;
+        Ok(updated.schema)
+    }
+    
+    /// Every collection, flagging the active one
+    pub async fn list_collections(&self) -> Result<Vec<CollectionSummary>> {
+        let active_id = self.active_collection.read().await.id.clone();
+        let collections = self.collections.list().map_err(collection_error)?;
+        let indexes = self.indexes.read().await;
+        
+        Ok(collections
+            .into_iter()
+            .map(|mut info| {
+                // Indexes built before collections existed have no recorded count
+                if let Some(snapshot) = indexes.get(&info.id) {
+                    info.document_count = snapshot.document_count;
+                }
+                CollectionSummary {
+                    is_active: info.id == active_id,
+                    info,
+                }
+            })
+            .collect())
+    }
+    
+    /// Add an empty collection, embedded with the given model or the configured one
+    pub async fn create_collection(
+        &self,
+        name: &str,
+        embedding_model: Option<String>,
+    ) -> Result<CollectionInfo> {
+        let embedding_model = match embedding_model {
+            Some(model) => model,
+            None => self.provider.read().await.embedding_model.clone(),
+        };
+        
+        self.collections
+            .create(name, &embedding_model)
+            .map_err(collection_error)
+    }
+    
+    /// Make another collection the active one and apply its models
+    pub async fn switch_collection(&self, id: &str) -> Result<CollectionInfo> {
+        let collection = self.collections.set_active(id).map_err(collection_error)?;
+        self.activate(collection.clone()).await;
+        Ok(collection)
+    }
+    
+    /// Give a collection a new display name
+    pub async fn rename_collection(&self, id: &str, name: &str) -> Result<CollectionInfo> {
+        let collection = self
+            .collections
+            .rename(id, name)
+            .map_err(collection_error)?;
+        self.refresh_active(&collection).await;
+        Ok(collection)
+    }
+    
+    /// Delete a collection and its index, returning the collection now active
+    pub async fn delete_collection(&self, id: &str) -> Result<CollectionInfo> {
+        let was_active = self.active_collection.read().await.id == id;
+        let active = self.collections.delete(id).map_err(collection_error)?;
+        self.indexes.write().await.remove(id);
+        
+        if was_active {
+            self.activate(active.clone()).await;
+        }
+        Ok(active)
+    }
+    
+    /// Use a collection's embedding model and (if it has one) its chat model
+    async fn activate(&self, collection: CollectionInfo) {
+        self.provider.write().await.embedding_model = collection.embedding_model.clone();
+        if let Some(model) = &collection.chat_model {
+            *self.selected_model.write().await = model.clone();
+        }
+        self.watcher.set_target(watch_target(&collection));
+        *self.active_collection.write().await = collection;
+    }
+    
+    /// Keep the in-memory copy of the active collection in sync after a change
+    async fn refresh_active(&self, collection: &CollectionInfo) {
+        let mut active = self.active_collection.write().await;
+        if active.id == collection.id {
+            self.watcher.set_target(watch_target(collection));
+            *active = collection.clone();
+        }
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collection store failures never involve the provider, so any settings do
+fn collection_error(error: cy_dog_core::Error) -> CyDogError {
+    CyDogError::from_core(error, &ProviderConfig::default())
+}
+
+/// Everything a collection was built from
+fn watch_target(collection: &CollectionInfo) -> WatchTarget {
+    WatchTarget {
+        collection_id: collection.id.clone(),
+        paths: collection
+            .folders
+            .iter()
+            .chain(&collection.files)
+            .map(PathBuf::from)
+            .collect(),
+    }
+}
+
+/// Pair each snapshot's index with its collection name
+fn named_indexes(snapshots: &[(String, Arc<IndexSnapshot>)]) -> Vec<(&str, &VectorIndex)> {
+    snapshots
+        .iter()
+        .map(|(name, snapshot)| (name.as_str(), &snapshot.index))
+        .collect()
+}
+
+/// Status information returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppStatus {
+    pub is_indexed: bool,
+    pub document_count: usize,
+    pub data_folder: Option<String>,
+    /// Files (and Excel sheets) in the index with their row counts
+    pub sources: Vec<IndexedSource>,
+    pub selected_model: String,
+    /// The active collection
+    pub collection_id: String,
+    pub collection_name: String,
+    /// Saved settings that could not be read at startup and were reset
+    pub warnings: Vec<String>,
+}
+
+/// One source file in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedSource {
+    pub source_file: String,
+    pub rows: usize,
+}
+
+/// Outcome of removing a source from the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceRemoval {
+    pub source_file: String,
+    pub documents_removed: usize,
+    /// Rows left in the index
+    pub document_count: usize,
+}
+
+/// What an automatic re-index changed; sent to the frontend as `index-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexChange {
+    pub collection_id: String,
+    /// Names of the files that changed
+    pub files: Vec<String>,
+    pub rows_added: usize,
+    pub rows_removed: usize,
+    /// Rows in the index afterwards
+    pub document_count: usize,
+}
+
+/// A collection as listed in the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionSummary {
+    #[serde(flatten)]
+    pub info: CollectionInfo,
+    pub is_active: bool,
+}
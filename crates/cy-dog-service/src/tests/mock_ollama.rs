@@ -0,0 +1,258 @@
+//! An in-process stand-in for the Ollama HTTP API.
+//!
+//! Embeddings are derived from hashed words, so texts that share words end up
+//! close together and retrieval behaves predictably. Chat replies are canned
+//! and name the model and the number of context rows the prompt contained.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// Length of the generated embedding vectors (matches nomic-embed-text)
+const DIMS: usize = 768;
+
+/// Context window reported by /api/show
+pub const CONTEXT_LENGTH: u64 = 8192;
+
+/// A running mock server; stops when dropped
+pub struct MockOllama {
+    addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+struct Installed {
+    models: Vec<String>,
+}
+
+impl Installed {
+    /// Ollama accepts a model name with or without the `:latest` tag
+    fn has(&self, model: &str) -> bool {
+        self.models
+            .iter()
+            .any(|m| m == model || m.strip_suffix(":latest") == Some(model))
+    }
+}
+
+impl MockOllama {
+    /// Serve on a random loopback port with the given models "pulled"
+    pub async fn start(models: &[&str]) -> Self {
+        let installed = Arc::new(Installed {
+            models: models.iter().map(|m| m.to_string()).collect(),
+        });
+
+        let router = Router::new()
+            .route("/api/tags", get(tags))
+            .route("/api/ps", get(ps))
+            .route("/api/show", post(show))
+            .route("/api/embed", post(embed))
+            .route("/api/chat", post(chat))
+            .with_state(installed);
+
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+
+        tokio::spawn(async move {
+            axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                })
+                .await
+                .unwrap();
+        });
+
+        Self {
+            addr,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for MockOllama {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// A loopback URL with nothing listening on it
+pub async fn unreachable_url() -> String {
+    let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .await
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{}", addr)
+}
+
+/// Deterministic bag-of-words embedding (FNV-1a hashed into `DIMS` buckets)
+fn embed_text(text: &str) -> Vec<f64> {
+    let mut vector = vec![0.0; DIMS];
+
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let hash = word
+            .to_lowercase()
+            .bytes()
+            .fold(0xcbf29ce484222325u64, |h, b| {
+                (h ^ b as u64).wrapping_mul(0x100000001b3)
+            });
+        vector[(hash % DIMS as u64) as usize] += 1.0;
+    }
+
+    let norm = vector.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+fn model_not_found(model: &str) -> Response {
+    let body = json!({ "error": format!("model \"{}\" not found, try pulling it first", model) });
+    (StatusCode::NOT_FOUND, Json(body)).into_response()
+}
+
+fn requested_model(body: &Value) -> String {
+    body["model"].as_str().unwrap_or_default().to_string()
+}
+
+async fn tags(State(installed): State<Arc<Installed>>) -> Json<Value> {
+    let models: Vec<Value> = installed
+        .models
+        .iter()
+        .map(|name| {
+            json!({
+                "name": name,
+                "size": 4_661_224_676u64,
+                "modified_at": "2024-05-01T12:00:00Z",
+                "details": {
+                    "family": "llama",
+                    "parameter_size": "8.0B",
+                    "quantization_level": "Q4_0",
+                },
+            })
+        })
+        .collect();
+    Json(json!({ "models": models }))
+}
+
+/// Reports the first installed model as loaded in memory
+async fn ps(State(installed): State<Arc<Installed>>) -> Json<Value> {
+    let models: Vec<Value> = installed
+        .models
+        .iter()
+        .take(1)
+        .map(|name| json!({ "name": name }))
+        .collect();
+    Json(json!({ "models": models }))
+}
+
+async fn show(State(installed): State<Arc<Installed>>, Json(body): Json<Value>) -> Response {
+    let model = requested_model(&body);
+    if !installed.has(&model) {
+        return model_not_found(&model);
+    }
+
+    Json(json!({
+        "model_info": {
+            "general.architecture": "llama",
+            "llama.context_length": CONTEXT_LENGTH,
+        }
+    }))
+    .into_response()
+}
+
+async fn embed(State(installed): State<Arc<Installed>>, Json(body): Json<Value>) -> Response {
+    let model = requested_model(&body);
+    if !installed.has(&model) {
+        return model_not_found(&model);
+    }
+
+    let inputs: Vec<String> = match &body["input"] {
+        Value::String(text) => vec![text.clone()],
+        Value::Array(texts) => texts
+            .iter()
+            .map(|t| t.as_str().unwrap_or_default().to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+    let embeddings: Vec<Vec<f64>> = inputs.iter().map(|text| embed_text(text)).collect();
+
+    Json(json!({
+        "model": model,
+        "embeddings": embeddings,
+        "total_duration": 0,
+        "load_duration": 0,
+        "prompt_eval_count": inputs.len(),
+    }))
+    .into_response()
+}
+
+/// The canned reply for a chat request
+fn canned_answer(model: &str, prompt: &str) -> String {
+    // Every retrieved row starts with "From <file>, Row <n>:"
+    let rows = prompt
+        .lines()
+        .filter(|line| line.starts_with("From "))
+        .count();
+    format!("Mock answer from {} based on {} rows.", model, rows)
+}
+
+async fn chat(State(installed): State<Arc<Installed>>, Json(body): Json<Value>) -> Response {
+    let model = requested_model(&body);
+    if !installed.has(&model) {
+        return model_not_found(&model);
+    }
+
+    let prompt = body["messages"]
+        .as_array()
+        .and_then(|messages| messages.iter().rev().find(|m| m["role"] == "user"))
+        .and_then(|m| m["content"].as_str())
+        .unwrap_or_default();
+    let answer = canned_answer(&model, prompt);
+
+    let reply = |content: &str, done: bool| {
+        json!({
+            "model": model,
+            "created_at": "2024-05-01T12:00:00Z",
+            "message": { "role": "assistant", "content": content, "tool_calls": [] },
+            "done": done,
+            "done_reason": "stop",
+            "total_duration": 0,
+            "load_duration": 0,
+            "prompt_eval_count": 0,
+            "prompt_eval_duration": 0,
+            "eval_count": 0,
+            "eval_duration": 0,
+        })
+    };
+
+    if body["stream"] == true {
+        // Newline-delimited JSON, one word per chunk
+        let mut lines: Vec<String> = answer
+            .split_inclusive(' ')
+            .map(|word| reply(word, false).to_string())
+            .collect();
+        lines.push(reply("", true).to_string());
+        return (StatusCode::OK, lines.join("\n")).into_response();
+    }
+
+    Json(reply(&answer, true)).into_response()
+}
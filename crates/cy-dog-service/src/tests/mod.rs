@@ -0,0 +1,549 @@
+//! End-to-end tests of the command logic against a mock Ollama server.
+
+mod mock_ollama;
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use cy_dog_core::{CollectionStore, ColumnRole, ProviderConfig, TOP_K_RESULTS};
+
+use crate::error::CyDogError;
+use crate::state::{AppState, IndexChange};
+use crate::watcher::{ChangeListener, WatchTarget};
+use mock_ollama::{unreachable_url, MockOllama, CONTEXT_LENGTH};
+
+const CHAT_MODEL: &str = "llama3:8b";
+const EMBEDDING_MODEL: &str = "nomic-embed-text:latest";
+
+/// A scratch directory unique to this test process and call
+fn scratch_dir(name: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "cy-dog-e2e-{}-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+        name
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// App state talking to `base_url`, persisting into a scratch directory
+async fn app_state(base_url: &str, name: &str) -> AppState {
+    let provider = ProviderConfig {
+        base_url: base_url.to_string(),
+        ..ProviderConfig::default()
+    };
+    let state = AppState::with_collections(provider, CollectionStore::new(scratch_dir(name)));
+    *state.selected_model.write().await = CHAT_MODEL.to_string();
+    state
+}
+
+/// A folder holding a small interview CSV
+fn interview_folder() -> String {
+    let dir = scratch_dir("data");
+    fs::write(
+        dir.join("interviews.csv"),
+        "Name,Role,Pain Point\n\
+         Alice,Nurse,Scheduling shifts takes hours every week\n\
+         Bob,Pharmacist,Inventory counts are done on paper\n\
+         Carol,Nurse,Handover notes get lost between shifts\n",
+    )
+    .unwrap();
+    dir.display().to_string()
+}
+
+#[tokio::test]
+async fn test_ingest_then_ask_cites_matching_rows() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "ask").await;
+
+    let result = state.ingest(interview_folder()).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.documents_ingested, 3);
+    assert_eq!(result.files_processed, 1);
+    assert_eq!(state.status().await.document_count, 3);
+
+    let answer = state
+        .ask("Which pharmacist does inventory on paper?", 1, &[])
+        .await
+        .unwrap();
+    assert_eq!(answer.sources, vec!["interviews.csv, Row 2"]);
+    assert_eq!(answer.answer, "Mock answer from llama3:8b based on 1 rows.");
+
+    let answer = state.ask("nurse shifts", TOP_K_RESULTS, &[]).await.unwrap();
+    assert_eq!(answer.sources.len(), 3);
+}
+
+#[tokio::test]
+async fn test_reingest_swaps_snapshot_under_readers() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "swap").await;
+    state.ingest(interview_folder()).await.unwrap();
+
+    // A query in flight keeps its snapshot while a new dataset replaces it
+    let in_flight = state.snapshot().await.unwrap();
+
+    let folder = scratch_dir("data-small");
+    fs::write(folder.join("one.csv"), "Name,Note\nDana,Wants exports\n").unwrap();
+    let folder = folder.display().to_string();
+    state.ingest(folder.clone()).await.unwrap();
+
+    assert_eq!(in_flight.document_count, 3);
+    let status = state.status().await;
+    assert_eq!(status.document_count, 1);
+    assert_eq!(status.data_folder, Some(folder));
+}
+
+#[tokio::test]
+async fn test_ingest_persists_index_for_next_session() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let provider = ProviderConfig {
+        base_url: ollama.url(),
+        ..ProviderConfig::default()
+    };
+    let dir = scratch_dir("persist");
+
+    let state = AppState::with_collections(provider.clone(), CollectionStore::new(&dir));
+    state.ingest(interview_folder()).await.unwrap();
+
+    let restored = AppState::with_collections(provider, CollectionStore::new(&dir));
+    let status = restored.status().await;
+    assert!(status.is_indexed);
+    assert_eq!(status.document_count, 3);
+}
+
+#[tokio::test]
+async fn test_saved_embedding_model_applies_until_collection_is_indexed() {
+    let provider = ProviderConfig {
+        embedding_model: "mxbai-embed-large".to_string(),
+        ..ProviderConfig::default()
+    };
+    let dir = scratch_dir("saved-provider");
+
+    let state = AppState::with_collections(provider, CollectionStore::new(&dir));
+    assert_eq!(
+        state.provider.read().await.embedding_model,
+        "mxbai-embed-large"
+    );
+
+    let settings = ProviderConfig {
+        embedding_model: "bge-m3".to_string(),
+        ..ProviderConfig::default()
+    };
+    state.set_provider(settings).await.unwrap();
+    let collection = state.collections.active().unwrap();
+    assert_eq!(collection.embedding_model, "bge-m3");
+    assert_eq!(
+        ProviderConfig::load(&dir).unwrap().embedding_model,
+        "bge-m3"
+    );
+}
+
+#[tokio::test]
+async fn test_switching_provider_redirects_queries() {
+    let old = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&old.url(), "switch-provider").await;
+    state.ingest(interview_folder()).await.unwrap();
+    assert!(state.search("paper", 1, &[]).await.is_ok());
+
+    let new = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let settings = ProviderConfig {
+        base_url: new.url(),
+        ..ProviderConfig::default()
+    };
+    state.set_provider(settings).await.unwrap();
+    drop(old);
+
+    // Queries are embedded by the new server, not the stopped one
+    let docs = state.search("paper", 1, &[]).await.unwrap();
+    assert_eq!(docs[0].source_file, "interviews.csv");
+    let answer = state.ask("paper", 1, &[]).await.unwrap();
+    assert_eq!(answer.sources, vec!["interviews.csv, Row 2"]);
+}
+
+#[tokio::test]
+async fn test_collections_are_separate_and_queryable_together() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "collections").await;
+    state.ingest(interview_folder()).await.unwrap();
+
+    let product = state.create_collection("Product B", None).await.unwrap();
+    state.switch_collection(&product.id).await.unwrap();
+    assert!(!state.status().await.is_indexed);
+
+    let folder = scratch_dir("data-product-b");
+    fs::write(
+        folder.join("calls.csv"),
+        "Name,Note
+Dana,Inventory spreadsheets break every month
+",
+    )
+    .unwrap();
+    state.ingest(folder.display().to_string()).await.unwrap();
+
+    let status = state.status().await;
+    assert_eq!(status.collection_name, "Product B");
+    assert_eq!(status.document_count, 1);
+
+    // Rows from both collections compete on score and cite their collection
+    let both = vec!["default".to_string(), product.id.clone()];
+    let docs = state.search("inventory paper", 2, &both).await.unwrap();
+    let sources: Vec<&str> = docs.iter().map(|d| d.source_file.as_str()).collect();
+    assert_eq!(
+        sources,
+        vec!["Default / interviews.csv", "Product B / calls.csv"]
+    );
+
+    let answer = state.ask("inventory", TOP_K_RESULTS, &both).await.unwrap();
+    assert_eq!(answer.sources.len(), 4);
+
+    let active = state.delete_collection(&product.id).await.unwrap();
+    assert_eq!(active.id, "default");
+    assert_eq!(state.status().await.document_count, 3);
+
+    let error = state.ask("inventory", 1, &both).await.unwrap_err();
+    assert_eq!(error.code(), "COLLECTION_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_add_and_remove_sources_keep_ids_stable() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "sources").await;
+    state.ingest(interview_folder()).await.unwrap();
+    let ids_before = document_ids(&state).await;
+
+    let extra = scratch_dir("data-extra").join("followups.csv");
+    fs::write(&extra, "Name,Note\nDana,Wants exports\nEli,Needs SSO\n").unwrap();
+    let extra = extra.display().to_string();
+
+    let result = state.add_sources(vec![extra.clone()]).await.unwrap();
+    assert_eq!(result.documents_ingested, 2);
+    let ids = document_ids(&state).await;
+    assert_eq!(ids[..3], ids_before[..]);
+    assert_eq!(ids[3..], ["doc_3", "doc_4"]);
+
+    // Adding a file again replaces its rows instead of duplicating them
+    state.add_sources(vec![extra]).await.unwrap();
+    assert_eq!(state.status().await.document_count, 5);
+
+    // Another folder's file with an indexed name must not replace its rows
+    let other = scratch_dir("data-other").join("interviews.csv");
+    fs::write(&other, "Name,Note\nFay,Unrelated\n").unwrap();
+    let error = state
+        .add_sources(vec![other.display().to_string()])
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), "INVALID_INPUT");
+    assert_eq!(state.status().await.document_count, 5);
+
+    let removal = state.remove_source("interviews.csv").await.unwrap();
+    assert_eq!(removal.documents_removed, 3);
+    assert_eq!(removal.document_count, 2);
+    let status = state.status().await;
+    assert_eq!(status.sources.len(), 1);
+    assert_eq!(status.sources[0].source_file, "followups.csv");
+
+    let error = state.remove_source("interviews.csv").await.unwrap_err();
+    assert_eq!(error.code(), "SOURCE_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_reindex_changed_files() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "watch").await;
+    let folder = PathBuf::from(interview_folder());
+    state.ingest(folder.display().to_string()).await.unwrap();
+
+    let created = folder.join("followups.csv");
+    fs::write(&created, "Name,Note\nDana,Wants exports\nEli,Needs SSO\n").unwrap();
+    let change = state
+        .reindex_changed("default", vec![created.clone()])
+        .await
+        .unwrap();
+    assert_eq!(change.files, vec!["followups.csv"]);
+    assert_eq!((change.rows_added, change.rows_removed), (2, 0));
+    assert_eq!(change.document_count, 5);
+
+    // Files inside a watched folder are not tracked individually
+    assert_eq!(state.watch_target().await.paths, vec![folder.clone()]);
+
+    fs::remove_file(&created).unwrap();
+    let change = state
+        .reindex_changed("default", vec![created])
+        .await
+        .unwrap();
+    assert_eq!((change.rows_added, change.rows_removed), (0, 2));
+    assert_eq!(state.status().await.document_count, 3);
+}
+
+#[tokio::test]
+async fn test_reindex_drops_deleted_and_emptied_files() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "evict").await;
+    let folder = PathBuf::from(interview_folder());
+    fs::write(
+        folder.join("followups.csv"),
+        "Name,Note\nDana,Wants exports\nEli,Needs SSO\n",
+    )
+    .unwrap();
+    fs::write(folder.join("extra.csv"), "Name,Note\nFay,Prefers email\n").unwrap();
+    state.ingest(folder.display().to_string()).await.unwrap();
+    assert_eq!(state.status().await.document_count, 6);
+
+    // Deleted while nothing watched the folder, and left with a header only
+    fs::remove_file(folder.join("followups.csv")).unwrap();
+    fs::write(folder.join("extra.csv"), "Name,Note\n").unwrap();
+
+    let result = state.reindex_collection().await.unwrap();
+    assert_eq!(result.documents_ingested, 3);
+    let status = state.status().await;
+    assert_eq!(status.document_count, 3);
+    let sources: Vec<_> = status
+        .sources
+        .iter()
+        .map(|s| s.source_file.as_str())
+        .collect();
+    assert_eq!(sources, ["interviews.csv"]);
+}
+
+#[tokio::test]
+async fn test_watched_changes_stay_with_their_collection() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = Arc::new(app_state(&ollama.url(), "watch-switch").await);
+    let folder = PathBuf::from(interview_folder());
+    state.ingest(folder.display().to_string()).await.unwrap();
+
+    let (sender, mut changes) = mpsc::unbounded_channel();
+    let listener: ChangeListener = Arc::new(move |change| {
+        let _ = sender.send(change);
+    });
+    let target = state.watch_target().await;
+    state
+        .watcher
+        .start(state.clone(), target, listener)
+        .unwrap();
+
+    // A file changes, then another collection becomes active before the
+    // change is re-indexed
+    fs::write(
+        folder.join("followups.csv"),
+        "Name,Note\nDana,Wants exports\n",
+    )
+    .unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let product = state.create_collection("Product B", None).await.unwrap();
+    state.switch_collection(&product.id).await.unwrap();
+
+    let change = next_change(&mut changes, Duration::from_secs(10)).await;
+    assert_eq!(change.collection_id, "default");
+    assert_eq!(change.document_count, 4);
+    assert!(!state.status().await.is_indexed);
+
+    // Stopping the watcher still re-indexes what changed before
+    state.switch_collection("default").await.unwrap();
+    fs::write(folder.join("more.csv"), "Name,Note\nEli,Needs SSO\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    state.watcher.stop();
+
+    let change = next_change(&mut changes, Duration::from_secs(1)).await;
+    assert_eq!(change.files, vec!["more.csv"]);
+    assert_eq!(state.status().await.document_count, 5);
+}
+
+#[tokio::test]
+async fn test_unwatchable_path_is_reported_to_the_listener() {
+    let state = Arc::new(app_state(&unreachable_url().await, "watch-missing").await);
+    let (sender, mut changes) = mpsc::unbounded_channel();
+    let listener: ChangeListener = Arc::new(move |change| {
+        let _ = sender.send(change);
+    });
+
+    let missing = scratch_dir("watch-missing-data").join("gone");
+    let target = WatchTarget {
+        collection_id: "default".to_string(),
+        paths: vec![missing],
+    };
+    let status = state
+        .watcher
+        .start(state.clone(), target, listener)
+        .unwrap();
+    assert!(status.paths.is_empty());
+
+    let error = changes.try_recv().unwrap().unwrap_err();
+    assert_eq!(error.code(), "WATCH_FAILED");
+    state.watcher.stop();
+}
+
+#[tokio::test]
+async fn test_column_roles_are_detected_and_applied() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "columns").await;
+
+    let folder = scratch_dir("data-columns");
+    fs::write(
+        folder.join("calls.csv"),
+        "ID,Interviewer,Date,Name,Note\n17,DM,2024-03-02,Dana,Wants exports\n",
+    )
+    .unwrap();
+    state.ingest(folder.display().to_string()).await.unwrap();
+
+    // IDs, dates and interviewer initials stay out of the embedded text
+    let doc = state.snapshot().await.unwrap().index.documents()[0]
+        .document
+        .clone();
+    assert_eq!(
+        doc.content,
+        "From calls.csv, Row 1: Name: Dana, Note: Wants exports"
+    );
+    assert_eq!(doc.metadata["ID"], "17");
+    assert_eq!(doc.metadata["Date"], "2024-03-02");
+    assert_eq!(doc.metadata["Name"], "Dana");
+
+    let schema = state
+        .set_column_role("Interviewer", ColumnRole::Text, None)
+        .await
+        .unwrap();
+    assert_eq!(schema.columns["Date"], ColumnRole::Date);
+
+    state.reindex_collection().await.unwrap();
+    let doc = state.snapshot().await.unwrap().index.documents()[0]
+        .document
+        .clone();
+    assert_eq!(doc.id, "doc_0");
+    assert!(doc.content.contains("Interviewer: DM"));
+}
+
+/// The next automatic re-index reported to a watcher's listener
+async fn next_change(
+    changes: &mut UnboundedReceiver<crate::error::Result<IndexChange>>,
+    within: Duration,
+) -> IndexChange {
+    tokio::time::timeout(within, changes.recv())
+        .await
+        .expect("no re-index reported")
+        .unwrap()
+        .unwrap()
+}
+
+/// Ids of the indexed documents, in index order
+async fn document_ids(state: &AppState) -> Vec<String> {
+    let snapshot = state.snapshot().await.unwrap();
+    snapshot
+        .index
+        .documents()
+        .iter()
+        .map(|doc| doc.document.id.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_ingest_missing_folder() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "missing-folder").await;
+
+    let error = state
+        .ingest("/definitely/not/a/folder".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), "FOLDER_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_ask_before_ingest() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "no-index").await;
+
+    let error = state.ask("anything", TOP_K_RESULTS, &[]).await.unwrap_err();
+    assert!(matches!(error, CyDogError::NoIndex));
+}
+
+#[tokio::test]
+async fn test_list_models_filters_embedding_models() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL, "mistral:7b"]).await;
+    let state = app_state(&ollama.url(), "models").await;
+
+    let models = state.list_models().await.unwrap();
+    let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec![CHAT_MODEL, "mistral:7b"]);
+
+    // The mock reports the first model as loaded and every model's context window
+    assert!(models[0].is_loaded);
+    assert!(!models[1].is_loaded);
+    assert_eq!(models[0].context_length, Some(CONTEXT_LENGTH));
+    assert_eq!(models[0].parameter_size.as_deref(), Some("8.0B"));
+}
+
+#[tokio::test]
+async fn test_status_ready_and_missing_embedding_model() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let status = app_state(&ollama.url(), "status")
+        .await
+        .provider_status()
+        .await;
+    assert!(status.is_running);
+    assert!(status.has_embedding_model);
+    assert_eq!(status.chat_models_count, 1);
+
+    let ollama = MockOllama::start(&[CHAT_MODEL]).await;
+    let status = app_state(&ollama.url(), "status-missing")
+        .await
+        .provider_status()
+        .await;
+    assert!(status.is_running);
+    assert!(!status.has_embedding_model);
+    assert!(status.message.contains("ollama pull nomic-embed-text"));
+}
+
+#[tokio::test]
+async fn test_ollama_down() {
+    let url = unreachable_url().await;
+    let state = app_state(&url, "down").await;
+
+    let status = state.provider_status().await;
+    assert!(!status.is_running);
+
+    let error = state.list_models().await.unwrap_err();
+    assert_eq!(error.code(), "PROVIDER_UNREACHABLE");
+    assert_eq!(
+        error.to_string(),
+        format!("Could not reach Ollama at {}. Is it running?", url)
+    );
+
+    let error = state.ingest(interview_folder()).await.unwrap_err();
+    assert_eq!(error.code(), "PROVIDER_UNREACHABLE");
+    assert!(!state.status().await.is_indexed);
+}
+
+#[tokio::test]
+async fn test_missing_embedding_model_on_ingest() {
+    let ollama = MockOllama::start(&[CHAT_MODEL]).await;
+    let state = app_state(&ollama.url(), "no-embedder").await;
+
+    let error = state.ingest(interview_folder()).await.unwrap_err();
+    match error {
+        CyDogError::ModelMissing { model } => assert_eq!(model, "nomic-embed-text"),
+        other => panic!("expected MODEL_MISSING, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_missing_chat_model_on_ask() {
+    let ollama = MockOllama::start(&[CHAT_MODEL, EMBEDDING_MODEL]).await;
+    let state = app_state(&ollama.url(), "no-chat-model").await;
+    state.ingest(interview_folder()).await.unwrap();
+
+    *state.selected_model.write().await = "phi3".to_string();
+    let error = state
+        .ask("paper inventory", TOP_K_RESULTS, &[])
+        .await
+        .unwrap_err();
+    match error {
+        CyDogError::ModelMissing { model } => assert_eq!(model, "phi3"),
+        other => panic!("expected MODEL_MISSING, got {:?}", other),
+    }
+}
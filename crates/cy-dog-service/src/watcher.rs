@@ -0,0 +1,237 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use cy_dog_core::is_supported_file;
+
+use crate::error::{CyDogError, Result};
+use crate::state::{AppState, IndexChange};
+
+/// Quiet period after the last file change before re-indexing
+const DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Receives the outcome of every automatic re-index, and paths or events
+/// the watcher failed on
+pub type ChangeListener = Arc<dyn Fn(Result<IndexChange>) + Send + Sync>;
+
+/// Optional watcher that re-indexes the active collection when its files change
+#[derive(Default)]
+pub struct FolderWatcher {
+    active: Mutex<Option<ActiveWatcher>>,
+}
+
+/// A collection and the folders and files it was built from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WatchTarget {
+    pub collection_id: String,
+    pub paths: Vec<PathBuf>,
+}
+
+impl WatchTarget {
+    /// Whether a changed file is one of the paths or directly inside one of
+    /// the folders (folders are flat, like the loader reads them)
+    fn covers(&self, path: &Path) -> bool {
+        self.paths
+            .iter()
+            .any(|watched| watched == path || path.parent() == Some(watched.as_path()))
+    }
+
+    /// The changed files this target follows, tagged with its collection
+    fn tag(&self, paths: Vec<PathBuf>) -> FileChange {
+        FileChange {
+            collection_id: self.collection_id.clone(),
+            paths: paths
+                .into_iter()
+                .filter(|path| self.covers(path) && is_supported_file(path))
+                .collect(),
+        }
+    }
+}
+
+struct ActiveWatcher {
+    watcher: RecommendedWatcher,
+    /// What is watched now; shared with the event handler so each change is
+    /// tagged with the collection it belongs to when it happens
+    watched: Arc<Mutex<WatchTarget>>,
+    listener: ChangeListener,
+}
+
+/// Watcher status returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchStatus {
+    pub watching: bool,
+    /// Folders and individual files being watched
+    pub paths: Vec<String>,
+}
+
+/// Changed files of one collection
+struct FileChange {
+    collection_id: String,
+    paths: Vec<PathBuf>,
+}
+
+type FileEvents = mpsc::UnboundedReceiver<notify::Result<FileChange>>;
+
+impl FolderWatcher {
+    /// Start watching `target` (if already running, only the target is updated)
+    pub fn start(
+        &self,
+        app: Arc<AppState>,
+        target: WatchTarget,
+        listener: ChangeListener,
+    ) -> Result<WatchStatus> {
+        let mut active = self.lock();
+
+        if active.is_none() {
+            let watched = Arc::new(Mutex::new(WatchTarget::default()));
+            let (sender, events) = mpsc::unbounded_channel();
+            let current = watched.clone();
+            let watcher =
+                notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                    if !event.as_ref().is_ok_and(|event| event.kind.is_access()) {
+                        let _ = sender.send(event.map(|event| lock(&current).tag(event.paths)));
+                    }
+                })
+                .map_err(|e| CyDogError::WatchFailed {
+                    message: e.to_string(),
+                })?;
+
+            tokio::spawn(reindex_on_change(app, events, listener.clone()));
+
+            *active = Some(ActiveWatcher {
+                watcher,
+                watched,
+                listener,
+            });
+        }
+
+        if let Some(active) = active.as_mut() {
+            active.watch(target);
+        }
+        Ok(status_of(active.as_ref()))
+    }
+
+    /// Stop watching; dropping the watcher also ends its re-index task once
+    /// the changes already seen are re-indexed
+    pub fn stop(&self) {
+        self.lock().take();
+    }
+
+    /// Follow another collection or a new set of folders and files (no-op while stopped)
+    pub fn set_target(&self, target: WatchTarget) {
+        if let Some(active) = self.lock().as_mut() {
+            active.watch(target);
+        }
+    }
+
+    pub fn status(&self) -> WatchStatus {
+        status_of(self.lock().as_ref())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<ActiveWatcher>> {
+        lock(&self.active)
+    }
+}
+
+impl ActiveWatcher {
+    fn watch(&mut self, target: WatchTarget) {
+        let mut watched = lock(&self.watched);
+
+        for path in &watched.paths {
+            if !target.paths.contains(path) {
+                let _ = self.watcher.unwatch(path);
+            }
+        }
+
+        let mut paths = Vec::with_capacity(target.paths.len());
+        for path in target.paths {
+            if watched.paths.contains(&path) {
+                paths.push(path);
+                continue;
+            }
+            // Folders are flat, like the loader reads them
+            match self.watcher.watch(&path, RecursiveMode::NonRecursive) {
+                Ok(()) => paths.push(path),
+                Err(e) => (self.listener)(Err(CyDogError::WatchFailed {
+                    message: format!("cannot watch {}: {}", path.display(), e),
+                })),
+            }
+        }
+        *watched = WatchTarget {
+            collection_id: target.collection_id,
+            paths,
+        };
+    }
+
+    fn paths(&self) -> Vec<String> {
+        lock(&self.watched)
+            .paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn status_of(active: Option<&ActiveWatcher>) -> WatchStatus {
+    WatchStatus {
+        watching: active.is_some(),
+        paths: active.map(ActiveWatcher::paths).unwrap_or_default(),
+    }
+}
+
+/// Collect changed files until they have been quiet for `DEBOUNCE`, then
+/// re-index them in the collection they belong to
+async fn reindex_on_change(app: Arc<AppState>, mut events: FileEvents, listener: ChangeListener) {
+    let mut pending: HashMap<String, HashSet<PathBuf>> = HashMap::new();
+
+    loop {
+        let next = if pending.is_empty() {
+            events.recv().await
+        } else {
+            match tokio::time::timeout(DEBOUNCE, events.recv()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    flush(&app, &mut pending, &listener).await;
+                    continue;
+                }
+            }
+        };
+
+        match next {
+            Some(Ok(change)) if !change.paths.is_empty() => {
+                pending
+                    .entry(change.collection_id)
+                    .or_default()
+                    .extend(change.paths);
+            }
+            Some(Ok(_)) => {}
+            Some(Err(e)) => listener(Err(CyDogError::WatchFailed {
+                message: e.to_string(),
+            })),
+            // The watcher was stopped; what changed before still counts
+            None => {
+                flush(&app, &mut pending, &listener).await;
+                break;
+            }
+        }
+    }
+}
+
+async fn flush(
+    app: &AppState,
+    pending: &mut HashMap<String, HashSet<PathBuf>>,
+    listener: &ChangeListener,
+) {
+    for (collection_id, paths) in pending.drain() {
+        let paths = paths.into_iter().collect();
+        listener(app.reindex_changed(&collection_id, paths).await);
+    }
+}
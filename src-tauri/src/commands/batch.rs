@@ -0,0 +1,47 @@
+use tauri::State;
+use std::path::Path;
+use std::sync::Arc;
+
+use cy_dog_core::{
+    load_questions, run_batch, write_report, BatchOutput, ReportFormat, TOP_K_RESULTS,
+};
+
+use cy_dog_service::error::{CyDogError, Result};
+use cy_dog_service::state::AppState;
+
+/// Run every question in a file through the index and write a report (plus JSON) to disk
+#[tauri::command]
+pub async fn run_batch_questions(
+    questions_path: String,
+    output_path: String,
+    format: ReportFormat,
+    state: State<'_, Arc<AppState>>,
+) -> Result<BatchOutput> {
+    let provider = state.provider().await;
+    let settings = provider.config().clone();
+    
+    let questions = load_questions(Path::new(&questions_path))
+        .map_err(|e| CyDogError::from_core(e, &settings))?;
+    if questions.is_empty() {
+        return Err(CyDogError::invalid_input(format!(
+            "No questions found in {}",
+            questions_path
+        )));
+    }
+    
+    let snapshot = state.require_snapshot().await?;
+    
+    let model_name = state.selected_model.read().await.clone();
+    
+    let report = run_batch(
+        &snapshot.index,
+        &provider,
+        &model_name,
+        &questions,
+        TOP_K_RESULTS,
+    )
+    .await;
+    
+    write_report(&report, Path::new(&output_path), format)
+        .map_err(|e| CyDogError::from_core(e, &settings))
+}
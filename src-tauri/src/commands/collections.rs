@@ -0,0 +1,53 @@
+use tauri::State;
+use std::sync::Arc;
+
+use cy_dog_core::CollectionInfo;
+
+use cy_dog_service::error::Result;
+use cy_dog_service::state::{AppState, CollectionSummary};
+
+/// List every collection, flagging the active one
+#[tauri::command]
+pub async fn list_collections(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<CollectionSummary>> {
+    state.list_collections().await
+}
+
+/// Create an empty collection (embedded with the configured model unless one is given)
+#[tauri::command]
+pub async fn create_collection(
+    name: String,
+    embedding_model: Option<String>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CollectionInfo> {
+    state.create_collection(&name, embedding_model).await
+}
+
+/// Make a collection the one ingest and questions use by default
+#[tauri::command]
+pub async fn switch_collection(
+    id: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CollectionInfo> {
+    state.switch_collection(&id).await
+}
+
+/// Rename a collection (its id stays the same)
+#[tauri::command]
+pub async fn rename_collection(
+    id: String,
+    name: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CollectionInfo> {
+    state.rename_collection(&id, &name).await
+}
+
+/// Delete a collection and its index; returns the collection that is now active
+#[tauri::command]
+pub async fn delete_collection(
+    id: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<CollectionInfo> {
+    state.delete_collection(&id).await
+}
@@ -0,0 +1,55 @@
+use tauri::State;
+use std::sync::Arc;
+
+use cy_dog_core::{ColumnRole, DatasetSchema, IngestResult, NumberFormat};
+
+use cy_dog_service::error::Result;
+use cy_dog_service::state::AppState;
+
+/// How each column of the active collection is used when indexing
+#[tauri::command]
+pub async fn get_column_schema(
+    state: State<'_, Arc<AppState>>,
+) -> Result<DatasetSchema> {
+    Ok(state.column_schema().await)
+}
+
+/// Set a column's role for the active collection (or only for one of its files)
+#[tauri::command]
+pub async fn set_column_role(
+    column: String,
+    role: ColumnRole,
+    file: Option<String>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<DatasetSchema> {
+    state.set_column_role(&column, role, file.as_deref()).await
+}
+
+/// Include or exclude one sheet of a workbook in the active collection
+#[tauri::command]
+pub async fn set_sheet_included(
+    file: String,
+    sheet: String,
+    included: bool,
+    state: State<'_, Arc<AppState>>,
+) -> Result<DatasetSchema> {
+    state.set_sheet_included(&file, &sheet, included).await
+}
+
+/// Set how the numbers of a workbook column are written in the active collection
+#[tauri::command]
+pub async fn set_number_format(
+    column: String,
+    format: NumberFormat,
+    state: State<'_, Arc<AppState>>,
+) -> Result<DatasetSchema> {
+    state.set_number_format(&column, format).await
+}
+
+/// Re-read the active collection's data so new column roles, sheet selections and number formats take effect
+#[tauri::command]
+pub async fn reindex_collection(
+    state: State<'_, Arc<AppState>>,
+) -> Result<IngestResult> {
+    state.reindex_collection().await
+}
@@ -1,81 +1,58 @@
 use tauri::State;
+use std::path::Path;
 use std::sync::Arc;
 
-use crate::state::{AppState, IngestResult};
-use crate::rag::{load_csvs_from_directory, VectorIndex};
+use cy_dog_core::IngestResult;
 
-/// Ingest all CSV files from the specified folder and build the vector index
+use cy_dog_service::error::{CyDogError, Result};
+use cy_dog_service::state::{AppState, AppStatus, SourceRemoval};
+
+/// Ingest every supported file in the specified folder and build the vector index
 #[tauri::command]
 pub async fn ingest_csvs(
     folder_path: String,
     state: State<'_, Arc<AppState>>,
-) -> Result<IngestResult, String> {
-    // Load CSV documents
-    let documents = load_csvs_from_directory(&folder_path)
-        .map_err(|e| format!("Failed to load CSVs: {}", e))?;
-    
-    let doc_count = documents.len();
-    
-    if doc_count == 0 {
-        return Ok(IngestResult {
-            success: false,
-            documents_ingested: 0,
-            files_processed: 0,
-            message: "No CSV files found or all files were empty".to_string(),
-        });
-    }
-    
-    // Count unique files
-    let files: std::collections::HashSet<_> = documents
-        .iter()
-        .map(|d| d.source_file.clone())
-        .collect();
-    let file_count = files.len();
-    
-    // Build vector index
-    let index = VectorIndex::from_documents(documents)
-        .await
-        .map_err(|e| format!("Failed to build vector index: {}", e))?;
-    
-    // Update state
-    {
-        let mut idx = state.vector_index.write().await;
-        *idx = Some(index);
-    }
-    {
-        let mut folder = state.data_folder.write().await;
-        *folder = Some(folder_path);
+) -> Result<IngestResult> {
+    state.ingest(folder_path).await
+}
+
+/// Add individual spreadsheets to the current index (re-reading any already indexed)
+#[tauri::command]
+pub async fn add_files(
+    paths: Vec<String>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<IngestResult> {
+    if paths.is_empty() {
+        return Err(CyDogError::invalid_input("No files selected"));
     }
-    {
-        let mut count = state.document_count.write().await;
-        *count = doc_count;
+    state.add_sources(paths).await
+}
+
+/// Add every supported file in another folder to the current index
+#[tauri::command]
+pub async fn add_folder(
+    folder_path: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<IngestResult> {
+    if !Path::new(&folder_path).is_dir() {
+        return Err(CyDogError::FolderNotFound { path: folder_path });
     }
-    
-    Ok(IngestResult {
-        success: true,
-        documents_ingested: doc_count,
-        files_processed: file_count,
-        message: format!(
-            "Successfully indexed {} rows from {} CSV file(s)",
-            doc_count, file_count
-        ),
-    })
+    state.add_sources(vec![folder_path]).await
+}
+
+/// Remove every row that came from a file (or a single Excel sheet, "file (Sheet)")
+#[tauri::command]
+pub async fn remove_source(
+    source_file: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<SourceRemoval> {
+    state.remove_source(&source_file).await
 }
 
 /// Get the current ingestion status
 #[tauri::command]
 pub async fn get_status(
     state: State<'_, Arc<AppState>>,
-) -> Result<crate::state::AppStatus, String> {
-    let is_indexed = state.vector_index.read().await.is_some();
-    let document_count = *state.document_count.read().await;
-    let data_folder = state.data_folder.read().await.clone();
-    let selected_model = state.selected_model.read().await.clone();
-    
-    Ok(crate::state::AppStatus {
-        is_indexed,
-        document_count,
-        data_folder,
-        selected_model,
-    })
+) -> Result<AppStatus> {
+    Ok(state.status().await)
 }
@@ -1,7 +1,19 @@
+pub mod batch;
+pub mod collections;
+pub mod columns;
 pub mod ingest;
 pub mod query;
 pub mod models;
+pub mod server;
+pub mod settings;
+pub mod watch;
 
+pub use batch::*;
+pub use collections::*;
+pub use columns::*;
 pub use ingest::*;
 pub use query::*;
 pub use models::*;
+pub use server::*;
+pub use settings::*;
+pub use watch::*;
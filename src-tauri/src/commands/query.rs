@@ -1,55 +1,38 @@
 use tauri::State;
 use std::sync::Arc;
 
-use crate::state::{AppState, QueryResult};
-use crate::rag::generate_rag_response;
+use cy_dog_core::{QueryResult, TOP_K_RESULTS};
 
-/// Number of similar documents to retrieve for context
-const TOP_K_RESULTS: usize = 5;
+use cy_dog_service::error::Result;
+use cy_dog_service::state::AppState;
 
-/// Ask a question and get a RAG-powered answer
+/// Ask a question and get a RAG-powered answer from the given collections
+/// (the active one if none are given)
 #[tauri::command]
 pub async fn ask_question(
     query: String,
+    collections: Option<Vec<String>>,
     state: State<'_, Arc<AppState>>,
-) -> Result<QueryResult, String> {
-    // Check if we have an index
-    let index_guard = state.vector_index.read().await;
-    let index = index_guard
-        .as_ref()
-        .ok_or_else(|| "No data has been indexed yet. Please ingest CSV files first.".to_string())?;
-    
-    // Search for relevant documents
-    let relevant_docs = index
-        .search(&query, TOP_K_RESULTS)
+) -> Result<QueryResult> {
+    state
+        .ask(&query, TOP_K_RESULTS, &collections.unwrap_or_default())
         .await
-        .map_err(|e| format!("Search failed: {}", e))?;
-    
-    if relevant_docs.is_empty() {
-        return Ok(QueryResult {
-            answer: "No relevant information found in the indexed data.".to_string(),
-            sources: vec![],
-        });
-    }
-    
-    // Get the selected model
-    let model_name = state.selected_model.read().await.clone();
-    
-    // Generate response
-    let (answer, sources) = generate_rag_response(&query, relevant_docs, &model_name)
-        .await
-        .map_err(|e| format!("Failed to generate response: {}", e))?;
-    
-    Ok(QueryResult { answer, sources })
 }
 
-/// Set the chat model to use
+/// Set the chat model to use (remembered for the active collection)
 #[tauri::command]
 pub async fn set_chat_model(
     model_name: String,
     state: State<'_, Arc<AppState>>,
-) -> Result<(), String> {
-    let mut model = state.selected_model.write().await;
-    *model = model_name;
+) -> Result<()> {
+    state.set_chat_model(model_name).await
+}
+
+/// Cancel the question currently being answered (it fails with `CANCELLED`)
+#[tauri::command]
+pub async fn cancel_question(
+    state: State<'_, Arc<AppState>>,
+) -> Result<()> {
+    state.cancel_questions();
     Ok(())
 }
@@ -0,0 +1,36 @@
+use tauri::State;
+use std::sync::Arc;
+
+use cy_dog_service::error::Result;
+use cy_dog_service::server::{ApiServerStatus, DEFAULT_API_PORT};
+use cy_dog_service::state::AppState;
+
+/// Start the local HTTP API on 127.0.0.1
+#[tauri::command]
+pub async fn start_api_server(
+    port: Option<u16>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<ApiServerStatus> {
+    let app = state.inner().clone();
+    state
+        .api_server
+        .start(app, port.unwrap_or(DEFAULT_API_PORT))
+        .await
+}
+
+/// Stop the local HTTP API
+#[tauri::command]
+pub async fn stop_api_server(
+    state: State<'_, Arc<AppState>>,
+) -> Result<ApiServerStatus> {
+    state.api_server.stop().await;
+    state.api_server.status().await
+}
+
+/// Whether the local HTTP API is running, its URL and bearer token
+#[tauri::command]
+pub async fn get_api_server_status(
+    state: State<'_, Arc<AppState>>,
+) -> Result<ApiServerStatus> {
+    state.api_server.status().await
+}
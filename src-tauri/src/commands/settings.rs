@@ -0,0 +1,31 @@
+use tauri::State;
+use std::sync::Arc;
+
+use cy_dog_core::ProviderConfig;
+use cy_dog_service::error::{CyDogError, Result};
+use cy_dog_service::state::AppState;
+
+/// Get the current LLM provider settings
+#[tauri::command]
+pub async fn get_provider_settings(
+    state: State<'_, Arc<AppState>>,
+) -> Result<ProviderConfig> {
+    Ok(state.provider.read().await.clone())
+}
+
+/// Switch the LLM provider (Ollama or an OpenAI-compatible server); the
+/// settings are saved for the next launch
+#[tauri::command]
+pub async fn set_provider_settings(
+    settings: ProviderConfig,
+    state: State<'_, Arc<AppState>>,
+) -> Result<()> {
+    if settings.base_url.trim().is_empty() {
+        return Err(CyDogError::invalid_input("Provider base URL cannot be empty"));
+    }
+    if settings.embedding_model.trim().is_empty() {
+        return Err(CyDogError::invalid_input("Embedding model cannot be empty"));
+    }
+    
+    state.set_provider(settings).await
+}
@@ -0,0 +1,50 @@
+use tauri::{AppHandle, Emitter, State};
+use std::sync::Arc;
+
+use cy_dog_service::error::Result;
+use cy_dog_service::state::AppState;
+use cy_dog_service::watcher::{ChangeListener, WatchStatus};
+
+/// Event sent after the watcher re-indexed changed files
+const INDEX_CHANGED_EVENT: &str = "index-changed";
+
+/// Event sent when re-indexing changed files failed
+const INDEX_CHANGE_FAILED_EVENT: &str = "index-change-failed";
+
+/// Watch the active collection's folders and files and re-index them when they change
+#[tauri::command]
+pub async fn start_watching(
+    app_handle: AppHandle,
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchStatus> {
+    let listener: ChangeListener = Arc::new(move |change| {
+        let _ = match change {
+            Ok(change) => app_handle.emit(INDEX_CHANGED_EVENT, change),
+            Err(error) => app_handle.emit(
+                INDEX_CHANGE_FAILED_EVENT,
+                serde_json::to_value(&error).unwrap_or_default(),
+            ),
+        };
+    });
+
+    let app = state.inner().clone();
+    let target = state.watch_target().await;
+    state.watcher.start(app, target, listener)
+}
+
+/// Stop re-indexing on file changes
+#[tauri::command]
+pub async fn stop_watching(
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchStatus> {
+    state.watcher.stop();
+    Ok(state.watcher.status())
+}
+
+/// Whether the watcher is running and what it follows
+#[tauri::command]
+pub async fn get_watch_status(
+    state: State<'_, Arc<AppState>>,
+) -> Result<WatchStatus> {
+    Ok(state.watcher.status())
+}
@@ -1,13 +1,18 @@
 mod commands;
-mod rag;
-mod state;
 
 use std::sync::Arc;
-use state::AppState;
+use cy_dog_service::AppState;
 use commands::{
-    ingest_csvs, get_status,
-    ask_question, set_chat_model,
+    ingest_csvs, add_files, add_folder, remove_source, get_status,
+    ask_question, set_chat_model, cancel_question,
     list_available_models, check_ollama_status,
+    get_provider_settings, set_provider_settings,
+    run_batch_questions,
+    list_collections, create_collection, switch_collection,
+    rename_collection, delete_collection,
+    get_column_schema, set_column_role, set_sheet_included, set_number_format, reindex_collection,
+    start_api_server, stop_api_server, get_api_server_status,
+    start_watching, stop_watching, get_watch_status,
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -18,11 +23,34 @@ pub fn run() {
         .manage(Arc::new(AppState::new()))
         .invoke_handler(tauri::generate_handler![
             ingest_csvs,
+            add_files,
+            add_folder,
+            remove_source,
             get_status,
             ask_question,
             set_chat_model,
+            cancel_question,
             list_available_models,
             check_ollama_status,
+            get_provider_settings,
+            set_provider_settings,
+            run_batch_questions,
+            list_collections,
+            create_collection,
+            switch_collection,
+            rename_collection,
+            delete_collection,
+            get_column_schema,
+            set_column_role,
+            set_sheet_included,
+            set_number_format,
+            reindex_collection,
+            start_api_server,
+            stop_api_server,
+            get_api_server_status,
+            start_watching,
+            stop_watching,
+            get_watch_status,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,5 +0,0 @@
-pub mod csv_loader;
-pub mod embeddings;
-
-pub use csv_loader::*;
-pub use embeddings::*;
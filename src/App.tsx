@@ -15,16 +15,49 @@ import {
   MessageSquare,
   Trash2,
   X,
+  RefreshCw,
+  SlidersHorizontal,
 } from 'lucide-react';
 import { open } from '@tauri-apps/plugin-dialog';
-import type { OllamaModel, OllamaStatus, AppStatus, ChatMessage } from './types';
+import type {
+  ChatModel,
+  ProviderStatus,
+  AppStatus,
+  ChatMessage,
+  CollectionSummary,
+  IndexChange,
+  ColumnRole,
+  DatasetSchema,
+  IngestResult,
+  CsvDialect,
+  NumberFormat,
+} from './types';
 import {
   checkOllamaStatus,
   listAvailableModels,
   getStatus,
   ingestCsvs,
+  addFiles,
+  addFolder,
+  removeSource,
   askQuestion,
+  cancelQuestion,
   setChatModel,
+  listCollections,
+  createCollection,
+  switchCollection,
+  deleteCollection,
+  startWatching,
+  stopWatching,
+  getWatchStatus,
+  onIndexChanged,
+  getColumnSchema,
+  setColumnRole,
+  setSheetIncluded,
+  setNumberFormat,
+  reindexCollection,
+  describeError,
+  isCyDogError,
 } from './api';
 
 interface ChatThread {
@@ -56,17 +89,64 @@ const createNewThread = (title = 'New Chat'): ChatThread => ({
   updatedAt: new Date(),
 });
 
+const COLUMN_ROLES: { value: ColumnRole; label: string }[] = [
+  { value: 'text', label: 'Embedded text' },
+  { value: 'interviewee', label: 'Interviewee' },
+  { value: 'date', label: 'Date' },
+  { value: 'metadata', label: 'Metadata only' },
+  { value: 'ignored', label: 'Ignored' },
+];
+
+// Number formats offered for embedded workbook columns; keyed as in `cy-dog numbers`
+const NUMBER_FORMATS: { key: string; label: string; format: NumberFormat }[] = [
+  { key: 'plain', label: 'Numbers as is', format: {} },
+  { key: 'decimals:2', label: '2 decimals', format: { decimals: 2 } },
+  { key: 'percent', label: 'Percent', format: { percent: true } },
+  { key: 'percent:1', label: 'Percent, 1 decimal', format: { percent: true, decimals: 1 } },
+];
+
+const numberFormatKey = (format?: NumberFormat): string => {
+  if (!format || (format.decimals === undefined && !format.percent)) return 'plain';
+  const kind = format.percent ? 'percent' : 'decimals';
+  return format.decimals === undefined ? kind : `${kind}:${format.decimals}`;
+};
+
+// Toast text for a re-index triggered by file changes, e.g. "3 new rows indexed"
+const describeIndexChange = (change: IndexChange): string => {
+  const parts = [];
+  if (change.rows_added > 0) parts.push(`${change.rows_added} new rows indexed`);
+  if (change.rows_removed > 0) parts.push(`${change.rows_removed} rows removed`);
+  if (parts.length === 0) parts.push(`${change.files.join(', ')} re-indexed`);
+  return `${parts.join(', ')}.`;
+};
+
+const DELIMITER_NAMES: Record<string, string> = { ',': 'comma', ';': 'semicolon', '\t': 'tab', '|': 'pipe' };
+
+// How a CSV file was read, e.g. "windows-1252, semicolon-separated"; null for plain comma-separated UTF-8
+const describeDialect = (dialect: CsvDialect): string | null => {
+  if (dialect.encoding === 'UTF-8' && dialect.delimiter === ',' && dialect.quote === '"') return null;
+  const delimiter = DELIMITER_NAMES[dialect.delimiter] ?? 'custom';
+  const quote = dialect.quote === '"' ? '' : `, ${dialect.quote} quotes`;
+  return `${dialect.encoding}, ${delimiter}-separated${quote}`;
+};
+
 function App() {
   // State
-  const [ollamaStatus, setOllamaStatus] = useState<OllamaStatus | null>(null);
-  const [models, setModels] = useState<OllamaModel[]>([]);
+  const [ollamaStatus, setOllamaStatus] = useState<ProviderStatus | null>(null);
+  const [models, setModels] = useState<ChatModel[]>([]);
   const [selectedModel, setSelectedModel] = useState<string>('');
   const [appStatus, setAppStatus] = useState<AppStatus | null>(null);
+  const [collections, setCollections] = useState<CollectionSummary[]>([]);
+  const [newCollectionName, setNewCollectionName] = useState<string | null>(null);
+  const [searchAllCollections, setSearchAllCollections] = useState(false);
   const [threads, setThreads] = useState<ChatThread[]>(() => [createNewThread()]);
   const [activeThreadId, setActiveThreadId] = useState<string>('');
   const [inputValue, setInputValue] = useState('');
   const [isLoading, setIsLoading] = useState(false);
   const [isIngesting, setIsIngesting] = useState(false);
+  const [isWatching, setIsWatching] = useState(false);
+  const [columnSchema, setColumnSchema] = useState<DatasetSchema | null>(null);
+  const [rolesChanged, setRolesChanged] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [copiedMessageId, setCopiedMessageId] = useState<string | null>(null);
   const [isInitializing, setIsInitializing] = useState(true);
@@ -88,6 +168,21 @@ function App() {
     initializeApp();
   }, []);
 
+  // Report re-indexing done by the folder watcher
+  useEffect(() => {
+    const unlisten = onIndexChanged(
+      async (change) => {
+        setAppStatus(await getStatus());
+        setCollections(await listCollections());
+        showToast(describeIndexChange(change), 'success');
+      },
+      (err) => showToast(`Auto-update failed: ${describeError(err)}`, 'error'),
+    );
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
   useEffect(() => {
     if (!activeThreadId && threads.length > 0) {
       setActiveThreadId(threads[0].id);
@@ -141,6 +236,17 @@ function App() {
     }, options?.durationMs ?? 2600);
   };
 
+  const showIngestWarnings = (result: IngestResult) => {
+    const dialectNotes = Object.entries(result.dialects ?? {}).flatMap(([file, dialect]) => {
+      const description = describeDialect(dialect);
+      return description ? [`${file} read as ${description}`] : [];
+    });
+    const lines = [...dialectNotes, ...result.warnings];
+    if (lines.length > 0) {
+      showToast(lines.join('\n'), 'info', { durationMs: 8000 });
+    }
+  };
+
   const dismissToast = (id: string) => {
     setLeavingToastIds((prev) => (prev.includes(id) ? prev : [...prev, id]));
     window.setTimeout(() => {
@@ -168,18 +274,26 @@ function App() {
         const availableModels = await listAvailableModels();
         setModels(availableModels);
         
-        // Set default model
-        if (availableModels.length > 0) {
-          setSelectedModel(availableModels[0].name);
-          await setChatModel(availableModels[0].name);
-        }
-
         // Get app status
         const appStat = await getStatus();
         setAppStatus(appStat);
+        if (appStat.warnings.length > 0) {
+          showToast(`${appStat.warnings.join('\n')}\nDefaults are used instead.`, 'error', { durationMs: 8000 });
+        }
+        setCollections(await listCollections());
+        setIsWatching((await getWatchStatus()).watching);
+
+        // Keep the collection's chat model if it is installed, else use the first one
+        const remembered = availableModels.find((model) => model.name === appStat.selected_model);
+        if (remembered) {
+          setSelectedModel(remembered.name);
+        } else if (availableModels.length > 0) {
+          setSelectedModel(availableModels[0].name);
+          await setChatModel(availableModels[0].name);
+        }
       }
     } catch (err) {
-      setError(`Initialization failed: ${err}`);
+      setError(`Initialization failed: ${describeError(err)}`);
     } finally {
       setIsInitializing(false);
     }
@@ -190,16 +304,67 @@ function App() {
     try {
       await setChatModel(modelName);
     } catch (err) {
-      setError(`Failed to set model: ${err}`);
+      setError(`Failed to set model: ${describeError(err)}`);
+    }
+  };
+
+  const refreshCollections = async () => {
+    const appStat = await getStatus();
+    setAppStatus(appStat);
+    setSelectedModel(appStat.selected_model);
+    setCollections(await listCollections());
+    setColumnSchema(null);
+  };
+
+  const handleCollectionChange = async (id: string) => {
+    try {
+      await switchCollection(id);
+      await refreshCollections();
+    } catch (err) {
+      setError(`Failed to switch collection: ${describeError(err)}`);
+    }
+  };
+
+  const handleCreateCollection = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!newCollectionName?.trim()) return;
+
+    try {
+      const created = await createCollection(newCollectionName.trim());
+      await switchCollection(created.id);
+      await refreshCollections();
+      setNewCollectionName(null);
+      showToast(`Collection "${created.name}" created. Load a folder into it.`, 'success');
+    } catch (err) {
+      setError(`Failed to create collection: ${describeError(err)}`);
     }
   };
 
+  const handleDeleteCollection = () => {
+    if (!appStatus) return;
+    const { collection_id: id, collection_name: name } = appStatus;
+
+    showToast(`Delete collection "${name}" and its index?`, 'info', {
+      actionLabel: 'Delete',
+      durationMs: 6000,
+      onAction: async () => {
+        try {
+          await deleteCollection(id);
+          await refreshCollections();
+          showToast(`Collection "${name}" deleted.`, 'success');
+        } catch (err) {
+          setError(`Failed to delete collection: ${describeError(err)}`);
+        }
+      },
+    });
+  };
+
   const handleSelectFolder = async () => {
     try {
       const selected = await open({
         directory: true,
         multiple: false,
-        title: 'Select folder containing CSV files',
+        title: 'Select folder containing interview files',
       });
 
       if (selected && typeof selected === 'string') {
@@ -213,6 +378,7 @@ function App() {
           // Update app status
           const appStat = await getStatus();
           setAppStatus(appStat);
+          setCollections(await listCollections());
           
           const ingestMessage: ChatMessage = {
             id: crypto.randomUUID(),
@@ -226,19 +392,145 @@ function App() {
             messages: [...thread.messages, ingestMessage],
           }));
           showToast('Dataset indexed successfully.', 'success');
+          showIngestWarnings(result);
         } else {
           setError(result.message);
           showToast(result.message, 'error');
         }
       }
     } catch (err) {
-      setError(`Failed to ingest files: ${err}`);
+      setError(`Failed to ingest files: ${describeError(err)}`);
       showToast('Failed to load dataset.', 'error');
     } finally {
       setIsIngesting(false);
     }
   };
 
+  const handleAddSources = async (directory: boolean) => {
+    try {
+      const selected = await open(
+        directory
+          ? { directory: true, multiple: false, title: 'Select another folder to add' }
+          : {
+              multiple: true,
+              title: 'Select spreadsheets, notes, transcripts or PDFs to add',
+              filters: [
+                { name: 'Spreadsheets', extensions: ['csv', 'xlsx', 'xls', 'xlsm', 'xlsb', 'ods'] },
+                { name: 'JSON records', extensions: ['json', 'jsonl', 'ndjson'] },
+                { name: 'Notes', extensions: ['md', 'markdown', 'txt', 'docx'] },
+                { name: 'Transcripts', extensions: ['vtt', 'srt'] },
+                { name: 'PDF reports', extensions: ['pdf'] },
+              ],
+            },
+      );
+      if (!selected || (Array.isArray(selected) && selected.length === 0)) return;
+
+      setIsIngesting(true);
+      setError(null);
+      const result =
+        typeof selected === 'string'
+          ? directory
+            ? await addFolder(selected)
+            : await addFiles([selected])
+          : await addFiles(selected);
+
+      if (result.success) {
+        setAppStatus(await getStatus());
+        setCollections(await listCollections());
+        showToast(`${result.message}.`, 'success');
+        showIngestWarnings(result);
+      } else {
+        showToast(result.message, 'error');
+      }
+    } catch (err) {
+      setError(`Failed to add data: ${describeError(err)}`);
+    } finally {
+      setIsIngesting(false);
+    }
+  };
+
+  const handleRemoveSource = async (sourceFile: string) => {
+    try {
+      const removal = await removeSource(sourceFile);
+      setAppStatus(await getStatus());
+      setCollections(await listCollections());
+      showToast(`Removed ${removal.documents_removed} rows from ${removal.source_file}.`, 'success');
+    } catch (err) {
+      setError(`Failed to remove ${sourceFile}: ${describeError(err)}`);
+    }
+  };
+
+  const handleToggleColumns = async () => {
+    if (columnSchema) {
+      setColumnSchema(null);
+      return;
+    }
+    try {
+      setColumnSchema(await getColumnSchema());
+      setRolesChanged(false);
+    } catch (err) {
+      setError(`Failed to load columns: ${describeError(err)}`);
+    }
+  };
+
+  const handleRoleChange = async (column: string, role: ColumnRole) => {
+    try {
+      setColumnSchema(await setColumnRole(column, role));
+      setRolesChanged(true);
+    } catch (err) {
+      setError(`Failed to set column role: ${describeError(err)}`);
+    }
+  };
+
+  const handleSheetToggle = async (file: string, sheet: string, included: boolean) => {
+    try {
+      setColumnSchema(await setSheetIncluded(file, sheet, included));
+      setRolesChanged(true);
+    } catch (err) {
+      setError(`Failed to change sheet selection: ${describeError(err)}`);
+    }
+  };
+
+  const handleNumberFormatChange = async (column: string, key: string) => {
+    const option = NUMBER_FORMATS.find((f) => f.key === key);
+    if (!option) return;
+    try {
+      setColumnSchema(await setNumberFormat(column, option.format));
+      setRolesChanged(true);
+    } catch (err) {
+      setError(`Failed to set number format: ${describeError(err)}`);
+    }
+  };
+
+  const handleApplyRoles = async () => {
+    setIsIngesting(true);
+    setError(null);
+    try {
+      const result = await reindexCollection();
+      setAppStatus(await getStatus());
+      setRolesChanged(false);
+      showToast(`${result.message}.`, 'success');
+      showIngestWarnings(result);
+    } catch (err) {
+      setError(`Failed to re-index: ${describeError(err)}`);
+    } finally {
+      setIsIngesting(false);
+    }
+  };
+
+  const handleToggleWatching = async () => {
+    try {
+      const status = isWatching ? await stopWatching() : await startWatching();
+      setIsWatching(status.watching);
+      showToast(
+        status.watching ? 'Watching for file changes.' : 'Stopped watching for file changes.',
+        'info',
+      );
+    } catch (err) {
+      setError(`Failed to toggle auto-update: ${describeError(err)}`);
+    }
+  };
+
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
     
@@ -264,7 +556,10 @@ function App() {
     setError(null);
 
     try {
-      const result = await askQuestion(userMessage.content);
+      const result = await askQuestion(
+        userMessage.content,
+        searchAllCollections ? collections.map((collection) => collection.id) : undefined,
+      );
       
       const assistantMessage: ChatMessage = {
         id: crypto.randomUUID(),
@@ -279,7 +574,11 @@ function App() {
         messages: [...thread.messages, assistantMessage],
       }));
     } catch (err) {
-      setError(`Query failed: ${err}`);
+      if (isCyDogError(err) && err.code === 'CANCELLED') {
+        showToast('Question cancelled.', 'info');
+      } else {
+        setError(`Query failed: ${describeError(err)}`);
+      }
     } finally {
       setIsLoading(false);
     }
@@ -292,6 +591,16 @@ function App() {
     return `${mb.toFixed(0)} MB`;
   };
 
+  const formatModelLabel = (model: ChatModel): string => {
+    const details = [
+      model.parameter_size,
+      model.quantization_level,
+      formatFileSize(model.size),
+      model.context_length ? `${Math.round(model.context_length / 1024)}k ctx` : null,
+    ].filter(Boolean);
+    return `${model.is_loaded ? '● ' : ''}${model.name} (${details.join(', ')})`;
+  };
+
   const handleNewChat = () => {
     const newThread = createNewThread();
     setThreads((prev) => [newThread, ...prev]);
@@ -394,6 +703,73 @@ function App() {
               </span>
             </div>
 
+            {/* Collection Selector */}
+            {collections.length > 0 && newCollectionName === null && (
+              <div className="flex items-center gap-1">
+                <div className="relative">
+                  <select
+                    value={appStatus?.collection_id ?? ''}
+                    onChange={(e) => handleCollectionChange(e.target.value)}
+                    title="Collection"
+                    className="appearance-none glass rounded-lg px-3 py-1.5 pr-8 text-sm text-[var(--text-primary)] focus:outline-none focus:ring-2 focus:ring-[var(--color-blue)]/30 cursor-pointer border-0"
+                  >
+                    {collections.map((collection) => (
+                      <option key={collection.id} value={collection.id}>
+                        {collection.name} ({collection.document_count} rows)
+                      </option>
+                    ))}
+                  </select>
+                  <ChevronDown className="absolute right-2 top-1/2 -translate-y-1/2 w-4 h-4 text-[var(--text-secondary)] pointer-events-none" />
+                </div>
+                <button
+                  onClick={() => setNewCollectionName('')}
+                  title="New collection"
+                  className="p-1.5 rounded-lg hover:bg-white/50 text-[var(--text-secondary)]"
+                >
+                  <Plus className="w-4 h-4" />
+                </button>
+                {collections.length > 1 && (
+                  <>
+                    <button
+                      onClick={handleDeleteCollection}
+                      title="Delete collection"
+                      className="p-1.5 rounded-lg hover:bg-white/50 text-[var(--text-secondary)]"
+                    >
+                      <Trash2 className="w-4 h-4" />
+                    </button>
+                    <label className="flex items-center gap-1.5 text-xs text-[var(--text-secondary)] cursor-pointer">
+                      <input
+                        type="checkbox"
+                        checked={searchAllCollections}
+                        onChange={(e) => setSearchAllCollections(e.target.checked)}
+                      />
+                      Ask all
+                    </label>
+                  </>
+                )}
+              </div>
+            )}
+            {newCollectionName !== null && (
+              <form onSubmit={handleCreateCollection} className="flex items-center gap-1">
+                <input
+                  autoFocus
+                  value={newCollectionName}
+                  onChange={(e) => setNewCollectionName(e.target.value)}
+                  onKeyDown={(e) => e.key === 'Escape' && setNewCollectionName(null)}
+                  placeholder="Collection name"
+                  className="glass rounded-lg px-3 py-1.5 text-sm text-[var(--text-primary)] focus:outline-none focus:ring-2 focus:ring-[var(--color-blue)]/30 border-0"
+                />
+                <button
+                  type="button"
+                  onClick={() => setNewCollectionName(null)}
+                  title="Cancel"
+                  className="p-1.5 rounded-lg hover:bg-white/50 text-[var(--text-secondary)]"
+                >
+                  <X className="w-4 h-4" />
+                </button>
+              </form>
+            )}
+
             {/* Model Selector */}
             {models.length > 0 && (
               <div className="relative">
@@ -404,7 +780,7 @@ function App() {
                 >
                   {models.map((model) => (
                     <option key={model.name} value={model.name}>
-                      {model.name} ({formatFileSize(model.size)})
+                      {formatModelLabel(model)}
                     </option>
                   ))}
                 </select>
@@ -509,25 +885,196 @@ function App() {
                 )}
               </div>
 
-              <button
-                onClick={handleSelectFolder}
-                disabled={isIngesting || !ollamaStatus?.is_running}
-                className="flex items-center gap-2 px-4 py-2 bg-gradient-to-r from-[var(--color-blue)] to-[var(--color-blue-light)] hover:shadow-lg hover:shadow-[var(--color-blue)]/25 disabled:opacity-50 disabled:cursor-not-allowed text-white text-sm font-medium rounded-xl transition-all duration-200 cursor-pointer"
-              >
-                {isIngesting ? (
-                  <>
-                    <Loader2 className="w-4 h-4 animate-spin" />
-                    Indexing...
-                  </>
-                ) : (
+              <div className="flex items-center gap-2">
+                {appStatus?.is_indexed && !isIngesting && (
                   <>
-                    <FolderOpen className="w-4 h-4" />
-                    {appStatus?.is_indexed ? 'Change Folder' : 'Select Folder'}
+                    <button
+                      onClick={() => handleAddSources(false)}
+                      disabled={!ollamaStatus?.is_running}
+                      title="Add spreadsheets to this index"
+                      className="flex items-center gap-1.5 px-3 py-2 glass hover:bg-white/80 disabled:opacity-50 disabled:cursor-not-allowed text-sm text-[var(--text-primary)] rounded-xl transition-all cursor-pointer"
+                    >
+                      <FileSpreadsheet className="w-4 h-4" />
+                      Add Files
+                    </button>
+                    <button
+                      onClick={() => handleAddSources(true)}
+                      disabled={!ollamaStatus?.is_running}
+                      title="Add another folder to this index"
+                      className="flex items-center gap-1.5 px-3 py-2 glass hover:bg-white/80 disabled:opacity-50 disabled:cursor-not-allowed text-sm text-[var(--text-primary)] rounded-xl transition-all cursor-pointer"
+                    >
+                      <Plus className="w-4 h-4" />
+                      Add Folder
+                    </button>
+                    <button
+                      onClick={handleToggleWatching}
+                      title={
+                        isWatching
+                          ? 'Stop re-indexing when files change'
+                          : 'Re-index automatically when files in the data folder change'
+                      }
+                      className={`flex items-center gap-1.5 px-3 py-2 glass hover:bg-white/80 text-sm rounded-xl transition-all cursor-pointer ${
+                        isWatching ? 'text-[var(--color-blue)] font-medium' : 'text-[var(--text-primary)]'
+                      }`}
+                    >
+                      <RefreshCw className="w-4 h-4" />
+                      {isWatching ? 'Auto-update on' : 'Auto-update'}
+                    </button>
+                    <button
+                      onClick={handleToggleColumns}
+                      title="Choose which columns are embedded, kept as metadata or ignored"
+                      className={`flex items-center gap-1.5 px-3 py-2 glass hover:bg-white/80 text-sm rounded-xl transition-all cursor-pointer ${
+                        columnSchema ? 'text-[var(--color-blue)] font-medium' : 'text-[var(--text-primary)]'
+                      }`}
+                    >
+                      <SlidersHorizontal className="w-4 h-4" />
+                      Columns
+                    </button>
                   </>
                 )}
-              </button>
+                <button
+                  onClick={handleSelectFolder}
+                  disabled={isIngesting || !ollamaStatus?.is_running}
+                  className="flex items-center gap-2 px-4 py-2 bg-gradient-to-r from-[var(--color-blue)] to-[var(--color-blue-light)] hover:shadow-lg hover:shadow-[var(--color-blue)]/25 disabled:opacity-50 disabled:cursor-not-allowed text-white text-sm font-medium rounded-xl transition-all duration-200 cursor-pointer"
+                >
+                  {isIngesting ? (
+                    <>
+                      <Loader2 className="w-4 h-4 animate-spin" />
+                      Indexing...
+                    </>
+                  ) : (
+                    <>
+                      <FolderOpen className="w-4 h-4" />
+                      {appStatus?.is_indexed ? 'Change Folder' : 'Select Folder'}
+                    </>
+                  )}
+                </button>
+              </div>
             </div>
 
+            {/* Indexed Sources */}
+            {appStatus && appStatus.sources.length > 1 && (
+              <div className="mb-4 -mt-2 flex flex-wrap gap-2">
+                {appStatus.sources.map((source) => (
+                  <span
+                    key={source.source_file}
+                    className="group flex items-center gap-1.5 glass rounded-full pl-3 pr-1.5 py-1 text-xs text-[var(--text-secondary)]"
+                  >
+                    {source.source_file}
+                    <span className="text-[var(--text-muted)]">{source.rows}</span>
+                    <button
+                      onClick={() => handleRemoveSource(source.source_file)}
+                      disabled={isIngesting}
+                      title={`Remove ${source.source_file} from the index`}
+                      className="p-0.5 rounded-full text-[var(--text-muted)] hover:text-[var(--color-red)] hover:bg-[var(--color-red)]/10 cursor-pointer"
+                    >
+                      <X className="w-3 h-3" />
+                    </button>
+                  </span>
+                ))}
+              </div>
+            )}
+
+            {/* Column Roles */}
+            {columnSchema && (
+              <div className="mb-4 glass rounded-xl p-3">
+                <div className="flex items-center justify-between mb-2">
+                  <span className="text-sm font-medium text-[var(--text-primary)]">Column roles</span>
+                  <div className="flex items-center gap-2">
+                    {rolesChanged && (
+                      <button
+                        onClick={handleApplyRoles}
+                        disabled={isIngesting || !ollamaStatus?.is_running}
+                        className="px-3 py-1 text-xs font-medium text-white bg-[var(--color-blue)] rounded-lg disabled:opacity-50 cursor-pointer"
+                      >
+                        {isIngesting ? 'Re-indexing...' : 'Re-index to apply'}
+                      </button>
+                    )}
+                    <button
+                      onClick={() => setColumnSchema(null)}
+                      title="Close"
+                      className="p-1 rounded-lg text-[var(--text-muted)] hover:bg-white/60 cursor-pointer"
+                    >
+                      <X className="w-4 h-4" />
+                    </button>
+                  </div>
+                </div>
+                {Object.keys(columnSchema.columns).length === 0 ? (
+                  <p className="text-xs text-[var(--text-secondary)]">
+                    Columns are detected the first time data is loaded into this collection.
+                  </p>
+                ) : (
+                  <div className="grid grid-cols-2 gap-x-4 gap-y-1.5">
+                    {Object.entries(columnSchema.columns).map(([column, role]) => (
+                      <label
+                        key={column}
+                        className="flex items-center justify-between gap-2 text-xs text-[var(--text-secondary)]"
+                      >
+                        <span className="truncate" title={column}>
+                          {column}
+                        </span>
+                        <span className="flex items-center gap-1">
+                          {role === 'text' && (
+                            <select
+                              value={numberFormatKey(columnSchema.number_formats?.[column])}
+                              onChange={(e) => handleNumberFormatChange(column, e.target.value)}
+                              title="How numbers in this column are written (workbooks only)"
+                              className="glass rounded-lg px-2 py-1 text-xs text-[var(--text-primary)] cursor-pointer"
+                            >
+                              {NUMBER_FORMATS.map(({ key, label }) => (
+                                <option key={key} value={key}>
+                                  {label}
+                                </option>
+                              ))}
+                            </select>
+                          )}
+                          <select
+                            value={role}
+                            onChange={(e) => handleRoleChange(column, e.target.value as ColumnRole)}
+                            className="glass rounded-lg px-2 py-1 text-xs text-[var(--text-primary)] cursor-pointer"
+                          >
+                            {COLUMN_ROLES.map(({ value, label }) => (
+                              <option key={value} value={value}>
+                                {label}
+                              </option>
+                            ))}
+                          </select>
+                        </span>
+                      </label>
+                    ))}
+                  </div>
+                )}
+                {columnSchema.sheets && Object.keys(columnSchema.sheets).length > 0 && (
+                  <div className="mt-3 pt-3 border-t border-white/40">
+                    <span className="text-sm font-medium text-[var(--text-primary)]">Sheets</span>
+                    {Object.entries(columnSchema.sheets).map(([file, sheets]) => (
+                      <div key={file} className="mt-1.5">
+                        <p className="text-xs text-[var(--text-muted)] truncate" title={file}>
+                          {file}
+                        </p>
+                        <div className="flex flex-wrap gap-x-4 gap-y-1 mt-1">
+                          {Object.entries(sheets).map(([sheet, included]) => (
+                            <label
+                              key={sheet}
+                              className="flex items-center gap-1.5 text-xs text-[var(--text-secondary)] cursor-pointer"
+                            >
+                              <input
+                                type="checkbox"
+                                checked={included}
+                                onChange={(e) => handleSheetToggle(file, sheet, e.target.checked)}
+                                className="cursor-pointer"
+                              />
+                              {sheet}
+                            </label>
+                          ))}
+                        </div>
+                      </div>
+                    ))}
+                  </div>
+                )}
+              </div>
+            )}
+
             {/* Error Display */}
             {error && (
               <div className="mb-4 p-3 bg-[var(--color-red)]/10 border border-[var(--color-red)]/20 rounded-xl flex items-center gap-2 text-sm text-[var(--color-red)]">
@@ -641,6 +1188,13 @@ function App() {
                     <div className="flex items-center gap-2 text-sm text-[var(--text-secondary)]">
                       <Loader2 className="w-4 h-4 animate-spin text-[var(--color-blue)]" />
                       Analyzing data...
+                      <button
+                        type="button"
+                        onClick={() => cancelQuestion()}
+                        className="ml-2 text-xs underline hover:text-[var(--text-primary)] cursor-pointer"
+                      >
+                        Stop
+                      </button>
                     </div>
                   </div>
                 </div>
@@ -698,7 +1252,7 @@ function App() {
                 }`}
               />
             </div>
-            <span className="flex-1">{toast.message}</span>
+            <span className="flex-1 whitespace-pre-line">{toast.message}</span>
             <button
               onClick={() => dismissToast(toast.id)}
               className="text-[var(--text-muted)] hover:text-[var(--text-secondary)] cursor-pointer"
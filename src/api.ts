@@ -1,18 +1,56 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type {
-  OllamaModel,
-  OllamaStatus,
+  ChatModel,
+  ProviderStatus,
   AppStatus,
   IngestResult,
   QueryResult,
+  ProviderConfig,
+  ReportFormat,
+  BatchOutput,
+  ApiServerStatus,
+  CyDogError,
+  CollectionInfo,
+  CollectionSummary,
+  SourceRemoval,
+  ColumnRole,
+  DatasetSchema,
+  NumberFormat,
+  WatchStatus,
+  IndexChange,
 } from './types';
 
-export async function checkOllamaStatus(): Promise<OllamaStatus> {
-  return invoke<OllamaStatus>('check_ollama_status');
+export function isCyDogError(err: unknown): err is CyDogError {
+  return typeof err === 'object' && err !== null && 'code' in err && 'message' in err;
 }
 
-export async function listAvailableModels(): Promise<OllamaModel[]> {
-  return invoke<OllamaModel[]>('list_available_models');
+// Human-readable message for a failed command, with a hint on how to fix it
+export function describeError(err: unknown): string {
+  if (!isCyDogError(err)) return String(err);
+
+  switch (err.code) {
+    case 'PROVIDER_UNREACHABLE':
+      return `${err.message} Start the server (\`ollama serve\` for Ollama) or check the provider URL in settings.`;
+    case 'MODEL_MISSING':
+      return `${err.message} Run \`ollama pull ${err.details?.model ?? '<model>'}\` and try again.`;
+    case 'NO_INDEX':
+      return 'No data has been indexed yet. Click "Load Data" to choose a folder.';
+    case 'FILE_PARSE':
+      return `${err.message} Fix or remove the file and load the folder again.`;
+    case 'EMBEDDING_MODEL_MISMATCH':
+      return `${err.message} Re-index the folder or switch back to the original embedding model.`;
+    default:
+      return err.message;
+  }
+}
+
+export async function checkOllamaStatus(): Promise<ProviderStatus> {
+  return invoke<ProviderStatus>('check_ollama_status');
+}
+
+export async function listAvailableModels(): Promise<ChatModel[]> {
+  return invoke<ChatModel[]>('list_available_models');
 }
 
 export async function getStatus(): Promise<AppStatus> {
@@ -23,10 +61,123 @@ export async function ingestCsvs(folderPath: string): Promise<IngestResult> {
   return invoke<IngestResult>('ingest_csvs', { folderPath });
 }
 
-export async function askQuestion(query: string): Promise<QueryResult> {
-  return invoke<QueryResult>('ask_question', { query });
+// Appends to the current index; files already indexed are re-read
+export async function addFiles(paths: string[]): Promise<IngestResult> {
+  return invoke<IngestResult>('add_files', { paths });
+}
+
+export async function addFolder(folderPath: string): Promise<IngestResult> {
+  return invoke<IngestResult>('add_folder', { folderPath });
+}
+
+export async function removeSource(sourceFile: string): Promise<SourceRemoval> {
+  return invoke<SourceRemoval>('remove_source', { sourceFile });
+}
+
+// Searches the active collection unless collection ids are given
+export async function askQuestion(query: string, collections?: string[]): Promise<QueryResult> {
+  return invoke<QueryResult>('ask_question', { query, collections });
+}
+
+export async function cancelQuestion(): Promise<void> {
+  return invoke<void>('cancel_question');
 }
 
 export async function setChatModel(modelName: string): Promise<void> {
   return invoke<void>('set_chat_model', { modelName });
 }
+
+export async function listCollections(): Promise<CollectionSummary[]> {
+  return invoke<CollectionSummary[]>('list_collections');
+}
+
+export async function createCollection(name: string, embeddingModel?: string): Promise<CollectionInfo> {
+  return invoke<CollectionInfo>('create_collection', { name, embeddingModel });
+}
+
+export async function switchCollection(id: string): Promise<CollectionInfo> {
+  return invoke<CollectionInfo>('switch_collection', { id });
+}
+
+export async function renameCollection(id: string, name: string): Promise<CollectionInfo> {
+  return invoke<CollectionInfo>('rename_collection', { id, name });
+}
+
+// Returns the collection that is active afterwards
+export async function deleteCollection(id: string): Promise<CollectionInfo> {
+  return invoke<CollectionInfo>('delete_collection', { id });
+}
+
+export async function getColumnSchema(): Promise<DatasetSchema> {
+  return invoke<DatasetSchema>('get_column_schema');
+}
+
+export async function setColumnRole(column: string, role: ColumnRole, file?: string): Promise<DatasetSchema> {
+  return invoke<DatasetSchema>('set_column_role', { column, role, file });
+}
+
+export async function setSheetIncluded(file: string, sheet: string, included: boolean): Promise<DatasetSchema> {
+  return invoke<DatasetSchema>('set_sheet_included', { file, sheet, included });
+}
+
+export async function setNumberFormat(column: string, format: NumberFormat): Promise<DatasetSchema> {
+  return invoke<DatasetSchema>('set_number_format', { column, format });
+}
+
+// Re-read the collection's data so changed column roles, sheets and number formats take effect
+export async function reindexCollection(): Promise<IngestResult> {
+  return invoke<IngestResult>('reindex_collection');
+}
+
+export async function getProviderSettings(): Promise<ProviderConfig> {
+  return invoke<ProviderConfig>('get_provider_settings');
+}
+
+export async function setProviderSettings(settings: ProviderConfig): Promise<void> {
+  return invoke<void>('set_provider_settings', { settings });
+}
+
+export async function runBatchQuestions(
+  questionsPath: string,
+  outputPath: string,
+  format: ReportFormat,
+): Promise<BatchOutput> {
+  return invoke<BatchOutput>('run_batch_questions', { questionsPath, outputPath, format });
+}
+
+export async function startApiServer(port?: number): Promise<ApiServerStatus> {
+  return invoke<ApiServerStatus>('start_api_server', { port });
+}
+
+export async function stopApiServer(): Promise<ApiServerStatus> {
+  return invoke<ApiServerStatus>('stop_api_server');
+}
+
+export async function getApiServerStatus(): Promise<ApiServerStatus> {
+  return invoke<ApiServerStatus>('get_api_server_status');
+}
+
+export async function startWatching(): Promise<WatchStatus> {
+  return invoke<WatchStatus>('start_watching');
+}
+
+export async function stopWatching(): Promise<WatchStatus> {
+  return invoke<WatchStatus>('stop_watching');
+}
+
+export async function getWatchStatus(): Promise<WatchStatus> {
+  return invoke<WatchStatus>('get_watch_status');
+}
+
+// Called after the watcher re-indexed changed files, or failed to
+export async function onIndexChanged(
+  onChange: (change: IndexChange) => void,
+  onError: (error: CyDogError) => void,
+): Promise<UnlistenFn> {
+  const unlistenChange = await listen<IndexChange>('index-changed', (event) => onChange(event.payload));
+  const unlistenError = await listen<CyDogError>('index-change-failed', (event) => onError(event.payload));
+  return () => {
+    unlistenChange();
+    unlistenError();
+  };
+}
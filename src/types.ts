@@ -1,23 +1,94 @@
 // Types for Tauri command responses
 
-export interface OllamaModel {
+export interface ChatModel {
   name: string;
   size: number;
   modified_at: string;
+  family: string | null;
+  parameter_size: string | null;
+  quantization_level: string | null;
+  context_length: number | null;
+  is_loaded: boolean;
 }
 
-export interface OllamaStatus {
+export interface ProviderStatus {
   is_running: boolean;
   has_embedding_model: boolean;
   chat_models_count: number;
   message: string;
 }
 
+export type ProviderKind = 'ollama' | 'open_ai_compatible';
+
+export interface ProviderConfig {
+  kind: ProviderKind;
+  base_url: string;
+  api_key: string | null;
+  embedding_model: string;
+}
+
 export interface AppStatus {
   is_indexed: boolean;
   document_count: number;
   data_folder: string | null;
+  sources: IndexedSource[];
   selected_model: string;
+  collection_id: string;
+  collection_name: string;
+  warnings: string[];
+}
+
+export interface IndexedSource {
+  source_file: string;
+  rows: number;
+}
+
+export interface SourceRemoval {
+  source_file: string;
+  documents_removed: number;
+  document_count: number;
+}
+
+export interface CollectionInfo {
+  id: string;
+  name: string;
+  folders: string[];
+  files: string[];
+  document_count: number;
+  embedding_model: string;
+  chat_model: string | null;
+  schema: DatasetSchema;
+  created_at: number;
+}
+
+// How a column is used when indexing: embedded, filter-only, or dropped
+export type ColumnRole = 'text' | 'metadata' | 'interviewee' | 'date' | 'ignored';
+
+export interface DatasetSchema {
+  // Roles by column header, for every file
+  columns: Record<string, ColumnRole>;
+  // Per-file overrides, by file name
+  files?: Record<string, Record<string, ColumnRole>>;
+  // Whether each workbook sheet is indexed, by file name then sheet name
+  sheets?: Record<string, Record<string, boolean>>;
+  // How the numbers of workbook columns are written, by column header
+  number_formats?: Record<string, NumberFormat>;
+}
+
+// Fixed decimals and/or fractions written as percentages (0.35 as "35%")
+export interface NumberFormat {
+  decimals?: number;
+  percent?: boolean;
+}
+
+export interface CollectionSummary extends CollectionInfo {
+  is_active: boolean;
+}
+
+export interface CsvDialect {
+  encoding: string;
+  delimiter: string;
+  quote: string;
 }
 
 export interface IngestResult {
@@ -25,6 +96,8 @@ export interface IngestResult {
   documents_ingested: number;
   files_processed: number;
   message: string;
+  warnings: string[];
+  dialects?: Record<string, CsvDialect>;
 }
 
 export interface QueryResult {
@@ -32,6 +105,62 @@ export interface QueryResult {
   sources: string[];
 }
 
+export type ReportFormat = 'markdown' | 'html';
+
+export interface BatchOutput {
+  questions: number;
+  failures: number;
+  report_path: string;
+  json_path: string;
+}
+
+export interface ApiServerStatus {
+  running: boolean;
+  url: string | null;
+  token: string;
+  error: string | null;
+}
+
+export interface WatchStatus {
+  watching: boolean;
+  paths: string[];
+}
+
+// Sent as the `index-changed` event after the watcher re-indexed changed files
+export interface IndexChange {
+  collection_id: string;
+  files: string[];
+  rows_added: number;
+  rows_removed: number;
+  document_count: number;
+}
+
+export type ErrorCode =
+  | 'PROVIDER_UNREACHABLE'
+  | 'MODEL_MISSING'
+  | 'NO_INDEX'
+  | 'COLLECTION_NOT_FOUND'
+  | 'SOURCE_NOT_FOUND'
+  | 'FOLDER_NOT_FOUND'
+  | 'FILE_PARSE'
+  | 'IO'
+  | 'EMBEDDING_FAILED'
+  | 'GENERATION_FAILED'
+  | 'INDEX_CORRUPT'
+  | 'EMBEDDING_MODEL_MISMATCH'
+  | 'INVALID_RESPONSE'
+  | 'CANCELLED'
+  | 'INVALID_INPUT'
+  | 'SERVER_FAILED'
+  | 'WATCH_FAILED';
+
+// Error returned by every command when it fails
+export interface CyDogError {
+  code: ErrorCode;
+  message: string;
+  details: Record<string, unknown> | null;
+}
+
 export interface ChatMessage {
   id: string;
   role: 'user' | 'assistant';